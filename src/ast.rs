@@ -1,18 +1,119 @@
-use std::fmt::Display;
+//! Markdown syntax tree.
 
-#[derive(Clone)]
+use std::fmt::{self, Display, Write};
+
+use crate::{event::Events, html, Options};
+
+pub use crate::options::SoftBreak;
+
+/// List type.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ListType {
     Unordered(char),
     Ordered(char, usize),
 }
 
-#[derive(Clone)]
+/// Code block type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CodeBlockType {
+    #[default]
+    Indented,
+    /// Fenced code block, with its fence character and length.
+    Fenced(char, usize),
+}
+
+/// List proximity.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ListProximity {
     Tight,
     Loose,
 }
 
-#[derive(Clone)]
+/// HTML rendering options.
+#[deprecated(note = "use `Options` instead")]
+#[derive(Clone, Copy, Default)]
+pub struct RenderOptions {
+    /// How to render soft line breaks.
+    pub soft_break: SoftBreak,
+    /// Whether to add `data-sourcepos` attributes to block elements.
+    pub source_positions: bool,
+}
+
+#[allow(deprecated)]
+impl From<RenderOptions> for Options {
+    fn from(options: RenderOptions) -> Self {
+        Options::new()
+            .soft_break(options.soft_break)
+            .source_positions(options.source_positions)
+    }
+}
+
+/// Point in the source text.
+///
+/// Lines and columns start at 1, and columns count bytes. Offsets are byte
+/// offsets from the start of the text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Point {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+impl Point {
+    pub fn new(line: usize, column: usize, offset: usize) -> Self {
+        Self {
+            line,
+            column,
+            offset,
+        }
+    }
+
+    /// Returns the point a number of bytes further along the same line.
+    pub(crate) fn add(self, n: usize) -> Self {
+        Self::new(self.line, self.column + n, self.offset + n)
+    }
+}
+
+impl Default for Point {
+    fn default() -> Self {
+        Self::new(1, 1, 0)
+    }
+}
+
+/// Span of the source text, from the start point up to but excluding the end
+/// point.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Position {
+    pub start: Point,
+    pub end: Point,
+}
+
+impl Position {
+    pub fn new(start: Point, end: Point) -> Self {
+        Self { start, end }
+    }
+}
+
+/// Table column alignment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+/// Front matter format, given by its delimiters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrontMatterKind {
+    /// YAML, delimited by `---`.
+    Yaml,
+    /// TOML, delimited by `+++`.
+    Toml,
+}
+
+/// HTML block type.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum HtmlType {
     Literal,
     Comment,
@@ -24,164 +125,248 @@ pub enum HtmlType {
 }
 
 /// Node.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Node {
     Root(Root),
 
     BlockQuote(BlockQuote),
     List(List),
     ListItem(ListItem),
+    FootnoteDefinition(FootnoteDefinition),
 
-    ThematicBreak,
+    FrontMatter(FrontMatter),
+    ThematicBreak(ThematicBreak),
     Heading(Heading),
     Paragraph(Paragraph),
     Code(Code),
     Html(Html),
-    Text(String),
+    Table(Table),
+    TableRow(TableRow),
+    TableCell(TableCell),
+
+    CodeSpan(CodeSpan),
+    Emphasis(Emphasis),
+    Strong(Strong),
+    Strikethrough(Strikethrough),
+    Link(Link),
+    Image(Image),
+    Autolink(Autolink),
+    FootnoteReference(FootnoteReference),
+    InlineHtml(InlineHtml),
+    SoftBreak(LineBreak),
+    HardBreak(LineBreak),
+    Text(Text),
 }
 
 impl Node {
+    /// Returns the children of the node, if it can have any.
+    pub fn children(&self) -> Option<&Vec<Node>> {
+        match self {
+            Node::Root(x) => Some(&x.children),
+            Node::BlockQuote(x) => Some(&x.children),
+            Node::List(x) => Some(&x.children),
+            Node::ListItem(x) => Some(&x.children),
+            Node::FootnoteDefinition(x) => Some(&x.children),
+            Node::Heading(x) => Some(&x.children),
+            Node::Paragraph(x) => Some(&x.children),
+            Node::Table(x) => Some(&x.children),
+            Node::TableRow(x) => Some(&x.children),
+            Node::TableCell(x) => Some(&x.children),
+            Node::Emphasis(x) => Some(&x.children),
+            Node::Strong(x) => Some(&x.children),
+            Node::Strikethrough(x) => Some(&x.children),
+            Node::Link(x) => Some(&x.children),
+            Node::Image(x) => Some(&x.children),
+            _ => None,
+        }
+    }
+
+    /// Returns the children of the node mutably, if it can have any.
     pub fn children_mut(&mut self) -> Option<&mut Vec<Node>> {
         match self {
             Node::Root(x) => Some(&mut x.children),
             Node::BlockQuote(x) => Some(&mut x.children),
             Node::List(x) => Some(&mut x.children),
             Node::ListItem(x) => Some(&mut x.children),
+            Node::FootnoteDefinition(x) => Some(&mut x.children),
+            Node::Heading(x) => Some(&mut x.children),
             Node::Paragraph(x) => Some(&mut x.children),
+            Node::Table(x) => Some(&mut x.children),
+            Node::TableRow(x) => Some(&mut x.children),
+            Node::TableCell(x) => Some(&mut x.children),
+            Node::Emphasis(x) => Some(&mut x.children),
+            Node::Strong(x) => Some(&mut x.children),
+            Node::Strikethrough(x) => Some(&mut x.children),
+            Node::Link(x) => Some(&mut x.children),
+            Node::Image(x) => Some(&mut x.children),
             _ => None,
         }
     }
-}
 
-lazy_static::lazy_static! {
-    static ref TIGHT: bool = false;
+    /// Returns the position of the node in the source text.
+    pub fn position(&self) -> Position {
+        match self {
+            Node::Root(x) => x.position,
+            Node::BlockQuote(x) => x.position,
+            Node::List(x) => x.position,
+            Node::ListItem(x) => x.position,
+            Node::FootnoteDefinition(x) => x.position,
+            Node::FrontMatter(x) => x.position,
+            Node::ThematicBreak(x) => x.position,
+            Node::Heading(x) => x.position,
+            Node::Paragraph(x) => x.position,
+            Node::Code(x) => x.position,
+            Node::Html(x) => x.position,
+            Node::Table(x) => x.position,
+            Node::TableRow(x) => x.position,
+            Node::TableCell(x) => x.position,
+            Node::CodeSpan(x) => x.position,
+            Node::Emphasis(x) => x.position,
+            Node::Strong(x) => x.position,
+            Node::Strikethrough(x) => x.position,
+            Node::Link(x) => x.position,
+            Node::Image(x) => x.position,
+            Node::Autolink(x) => x.position,
+            Node::FootnoteReference(x) => x.position,
+            Node::InlineHtml(x) => x.position,
+            Node::SoftBreak(x) => x.position,
+            Node::HardBreak(x) => x.position,
+            Node::Text(x) => x.position,
+        }
+    }
+
+    pub fn position_mut(&mut self) -> &mut Position {
+        match self {
+            Node::Root(x) => &mut x.position,
+            Node::BlockQuote(x) => &mut x.position,
+            Node::List(x) => &mut x.position,
+            Node::ListItem(x) => &mut x.position,
+            Node::FootnoteDefinition(x) => &mut x.position,
+            Node::FrontMatter(x) => &mut x.position,
+            Node::ThematicBreak(x) => &mut x.position,
+            Node::Heading(x) => &mut x.position,
+            Node::Paragraph(x) => &mut x.position,
+            Node::Code(x) => &mut x.position,
+            Node::Html(x) => &mut x.position,
+            Node::Table(x) => &mut x.position,
+            Node::TableRow(x) => &mut x.position,
+            Node::TableCell(x) => &mut x.position,
+            Node::CodeSpan(x) => &mut x.position,
+            Node::Emphasis(x) => &mut x.position,
+            Node::Strong(x) => &mut x.position,
+            Node::Strikethrough(x) => &mut x.position,
+            Node::Link(x) => &mut x.position,
+            Node::Image(x) => &mut x.position,
+            Node::Autolink(x) => &mut x.position,
+            Node::FootnoteReference(x) => &mut x.position,
+            Node::InlineHtml(x) => &mut x.position,
+            Node::SoftBreak(x) => &mut x.position,
+            Node::HardBreak(x) => &mut x.position,
+            Node::Text(x) => &mut x.position,
+        }
+    }
+
+    /// Set the position of the node in the source text.
+    pub fn with_position(mut self, position: Position) -> Self {
+        *self.position_mut() = position;
+        self
+    }
+
+    /// Returns the text of the node and its descendants without markup.
+    pub fn plain_text(&self) -> String {
+        match self {
+            Node::Code(x) => x.text.clone(),
+            Node::CodeSpan(x) => x.text.clone(),
+            Node::Autolink(x) => x.text.clone(),
+            Node::Text(x) => x.text.clone(),
+            Node::SoftBreak(_) | Node::HardBreak(_) => "\n".to_string(),
+            _ => self
+                .children()
+                .map(|children| children.iter().map(Node::plain_text).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Returns whether the node is inline content.
+    pub fn is_inline(&self) -> bool {
+        matches!(
+            self,
+            Node::CodeSpan(_)
+                | Node::Emphasis(_)
+                | Node::Strong(_)
+                | Node::Strikethrough(_)
+                | Node::Link(_)
+                | Node::Image(_)
+                | Node::Autolink(_)
+                | Node::FootnoteReference(_)
+                | Node::InlineHtml(_)
+                | Node::SoftBreak(_)
+                | Node::HardBreak(_)
+                | Node::Text(_)
+        )
+    }
 }
 
 impl Display for Node {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Node::Root(x) => x.children.iter().for_each(|c| write!(f, "{c}").unwrap()),
-            Node::BlockQuote(x) => {
-                write!(f, "<blockquote>\n").unwrap();
-                x.children.iter().for_each(|c| write!(f, "{c}").unwrap());
-                write!(f, "</blockquote>\n").unwrap();
-            }
-            Node::ThematicBreak => {
-                write!(f, "<hr />\n").unwrap();
-            }
-            Node::List(x) => {
-                let tag = match x.list_type {
-                    ListType::Unordered(_) => "ul",
-                    ListType::Ordered(_, _) => "ol",
-                };
-                let start = match x.list_type {
-                    ListType::Ordered(_, 1) => "".to_string(),
-                    ListType::Ordered(_, start) => format!(r#" start="{start}""#),
-                    _ => "".into(),
-                };
-                write!(f, "<{tag}{start}>\n").unwrap();
-                x.children.iter().for_each(|c| write!(f, "{c}").unwrap());
-                write!(f, "</{tag}>\n").unwrap();
-            }
-            Node::ListItem(x) => {
-                write!(f, "<li>").unwrap();
-                let mut skip = false;
-                x.children.iter().for_each(|c| {
-                    let newline = if matches!(c, Node::Text(_)) || skip {
-                        "".to_string()
-                    } else {
-                        "\n".to_string()
-                    };
-                    let buf = format!("{newline}{c}");
-                    if buf.ends_with("\n") {
-                        skip = true;
-                    } else {
-                        skip = false;
-                    }
-                    write!(f, "{buf}").unwrap();
-                });
-                write!(f, "</li>\n").unwrap();
-            }
-            Node::Heading(x) => {
-                let level = x.level;
-                write!(f, "<h{level}>").unwrap();
-                x.children.iter().for_each(|c| write!(f, "{c}").unwrap());
-                write!(f, "</h{level}>\n").unwrap();
-            }
-            Node::Paragraph(x) => {
-                write!(f, "<p>").unwrap();
-                x.children.iter().for_each(|c| write!(f, "{c}").unwrap());
-                write!(f, "</p>\n").unwrap();
-            }
-            Node::Code(x) => {
-                let info = match &x.info {
-                    Some(info) => {
-                        let i = info.trim().split(" ").next().unwrap();
-                        format!(r#" class="language-{i}""#)
-                    }
-                    None => "".to_string(),
-                };
-                write!(f, "<pre><code{}>{}</code></pre>\n", info, encode(&x.text)).unwrap();
-            }
-            Node::Html(x) => write!(f, "{}", x.text).unwrap(),
-            Node::Text(x) => {
-                write!(f, "{}", escape(x.trim_end())).unwrap();
-            }
-        };
-        Ok(())
-    }
-}
-
-const ESCAPES: [(&str, &str); 3] = [(r"\#", "#"), (r"\>", "&gt;"), (r"\-", "-")];
-
-fn escape(input: &str) -> String {
-    let mut output = input.to_string();
-    for (from, to) in ESCAPES {
-        output = output.replace(from, to);
-    }
-    output
-}
-
-const ENCODINGS: [(&str, &str); 2] = [("<", "&lt;"), (">", "&gt;")];
-
-fn encode(input: &str) -> String {
-    let mut output = input.to_string();
-    for (from, to) in ENCODINGS {
-        output = output.replace(from, to);
-    }
-    output
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(f, &Options::default())
+    }
+}
+
+impl Node {
+    /// Render the node as HTML with the given options.
+    pub fn render(&self, f: &mut dyn Write, options: &Options) -> fmt::Result {
+        html::write_html(f, Events::new(self).with_positions(), options)
+    }
 }
 
 /// Root.
-#[derive(Clone)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Root {
     pub children: Vec<Node>,
+    pub position: Position,
 }
 
 impl Root {
     pub fn new() -> Self {
-        Self { children: vec![] }
+        Self {
+            children: vec![],
+            position: Position::default(),
+        }
+    }
+
+    /// Returns the front matter of the document, if it has any.
+    pub fn front_matter(&self) -> Option<&FrontMatter> {
+        match self.children.first() {
+            Some(Node::FrontMatter(x)) => Some(x),
+            _ => None,
+        }
     }
 }
 
 /// Block quote.
-#[derive(Clone)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct BlockQuote {
-    children: Vec<Node>,
+    pub children: Vec<Node>,
+    pub position: Position,
 }
 
 impl BlockQuote {
     pub fn new() -> Self {
-        Self { children: vec![] }
+        Self {
+            children: vec![],
+            position: Position::default(),
+        }
     }
 }
 
 /// List.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct List {
     pub list_type: ListType,
     pub proximity: ListProximity,
     pub children: Vec<Node>,
+    pub position: Position,
 }
 
 impl List {
@@ -190,56 +375,158 @@ impl List {
             list_type,
             proximity: ListProximity::Tight,
             children: vec![],
+            position: Position::default(),
         }
     }
 }
 
 /// List item.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ListItem {
     pub indent: usize,
+    /// Whether the item is a checked task, if it is a task list item.
+    pub checked: Option<bool>,
     pub children: Vec<Node>,
+    pub position: Position,
 }
 
 impl ListItem {
     pub fn new(indent: usize) -> Self {
         Self {
+            indent,
+            checked: None,
+            children: vec![],
+            position: Position::default(),
+        }
+    }
+}
+
+/// Footnote definition.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FootnoteDefinition {
+    pub label: String,
+    pub indent: usize,
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl FootnoteDefinition {
+    pub fn new(label: String, indent: usize) -> Self {
+        Self {
+            label,
             indent,
             children: vec![],
+            position: Position::default(),
+        }
+    }
+}
+
+/// Front matter at the start of a document, which is not rendered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FrontMatter {
+    pub kind: FrontMatterKind,
+    /// The text between the delimiters.
+    pub text: String,
+    pub position: Position,
+}
+
+impl FrontMatter {
+    pub fn new(kind: FrontMatterKind, text: String) -> Self {
+        Self {
+            kind,
+            text,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Thematic break.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ThematicBreak {
+    pub position: Position,
+}
+
+impl ThematicBreak {
+    pub fn new() -> Self {
+        Self {
+            position: Position::default(),
         }
     }
 }
 
+/// Attributes from an attribute block, such as `{#id .class key=value}`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Attributes {
+    pub id: Option<String>,
+    pub classes: Vec<String>,
+    /// Other attributes, as names and values.
+    pub attrs: Vec<(String, String)>,
+}
+
+impl Attributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.classes.is_empty() && self.attrs.is_empty()
+    }
+}
+
 /// Heading.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Heading {
     pub level: u8,
+    /// The ID of the heading, if it has one.
+    pub id: Option<String>,
+    /// The classes and other attributes of the heading. An ID from its
+    /// attribute block is kept in `id` instead.
+    pub attributes: Attributes,
     pub children: Vec<Node>,
+    pub position: Position,
 }
 
 impl Heading {
     pub fn new(level: u8, children: Vec<Node>) -> Self {
-        Self { level, children }
+        Self {
+            level,
+            id: None,
+            attributes: Attributes::default(),
+            children,
+            position: Position::default(),
+        }
     }
 }
 
 /// Paragraph.
-#[derive(Clone)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Paragraph {
     pub children: Vec<Node>,
+    pub position: Position,
 }
 
 impl Paragraph {
     pub fn new() -> Self {
-        Self { children: vec![] }
+        Self {
+            children: vec![],
+            position: Position::default(),
+        }
     }
 }
 
 /// Code.
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct Code {
     pub text: String,
     pub info: Option<String>,
+    pub block_type: CodeBlockType,
+    /// The number of columns of indentation removed from the lines of the
+    /// block, which is that of the opening fence of a fenced code block.
+    pub indent: usize,
+    /// The attributes from the attribute block of a fenced code block.
+    pub attributes: Attributes,
+    pub position: Position,
 }
 
 impl Code {
@@ -251,14 +538,255 @@ impl Code {
 }
 
 /// HTML.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Html {
     pub text: String,
     pub html_type: HtmlType,
+    pub position: Position,
 }
 
 impl Html {
     pub fn new(text: String, html_type: HtmlType) -> Self {
-        Self { text, html_type }
+        Self {
+            text,
+            html_type,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Table.
+///
+/// The first row is the header row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Table {
+    pub alignments: Vec<Alignment>,
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl Table {
+    pub fn new(alignments: Vec<Alignment>) -> Self {
+        Self {
+            alignments,
+            children: vec![],
+            position: Position::default(),
+        }
+    }
+}
+
+/// Table row.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableRow {
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl TableRow {
+    pub fn new(children: Vec<Node>) -> Self {
+        Self {
+            children,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Table cell.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TableCell {
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl TableCell {
+    pub fn new(children: Vec<Node>) -> Self {
+        Self {
+            children,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Code span.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CodeSpan {
+    pub text: String,
+    pub position: Position,
+}
+
+impl CodeSpan {
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Emphasis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Emphasis {
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl Emphasis {
+    pub fn new(children: Vec<Node>) -> Self {
+        Self {
+            children,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Strong emphasis.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Strong {
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl Strong {
+    pub fn new(children: Vec<Node>) -> Self {
+        Self {
+            children,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Strikethrough.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Strikethrough {
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl Strikethrough {
+    pub fn new(children: Vec<Node>) -> Self {
+        Self {
+            children,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Link.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Link {
+    pub destination: String,
+    pub title: Option<String>,
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl Link {
+    pub fn new(destination: String, title: Option<String>, children: Vec<Node>) -> Self {
+        Self {
+            destination,
+            title,
+            children,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Image {
+    pub destination: String,
+    pub title: Option<String>,
+    pub children: Vec<Node>,
+    pub position: Position,
+}
+
+impl Image {
+    pub fn new(destination: String, title: Option<String>, children: Vec<Node>) -> Self {
+        Self {
+            destination,
+            title,
+            children,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Autolink.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Autolink {
+    pub destination: String,
+    pub text: String,
+    pub position: Position,
+}
+
+impl Autolink {
+    pub fn new(destination: String, text: String) -> Self {
+        Self {
+            destination,
+            text,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Footnote reference.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FootnoteReference {
+    pub label: String,
+    pub position: Position,
+}
+
+impl FootnoteReference {
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Inline HTML.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InlineHtml {
+    pub text: String,
+    pub position: Position,
+}
+
+impl InlineHtml {
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            position: Position::default(),
+        }
+    }
+}
+
+/// Line break.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LineBreak {
+    pub position: Position,
+}
+
+impl LineBreak {
+    pub fn new() -> Self {
+        Self {
+            position: Position::default(),
+        }
+    }
+}
+
+/// Text.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Text {
+    pub text: String,
+    pub position: Position,
+}
+
+impl Text {
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            position: Position::default(),
+        }
     }
 }
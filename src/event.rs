@@ -0,0 +1,267 @@
+//! Pull-based event stream over a syntax tree.
+//!
+//! [`Events`] walks a tree depth-first and yields an [`Event`] for each leaf
+//! node and a pair of [`Event::Start`] and [`Event::End`] events around the
+//! children of each parent node. The HTML renderer consumes the same stream.
+//!
+//! # Examples
+//!
+//! ```
+//! use markdown::event::{Event, Events, Tag};
+//!
+//! let root = markdown::parse("*hello*");
+//! let events: Vec<Event> = Events::new(&root).collect();
+//! assert_eq!(
+//!     events,
+//!     vec![
+//!         Event::Start(Tag::Paragraph),
+//!         Event::Start(Tag::Emphasis),
+//!         Event::Text("hello"),
+//!         Event::End(Tag::Emphasis),
+//!         Event::End(Tag::Paragraph),
+//!     ]
+//! );
+//! ```
+
+use crate::ast::{self, Alignment, Attributes, ListType, Node, Position};
+
+/// Parent node that wraps other events.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tag<'a> {
+    BlockQuote,
+    /// List, with the start number of an ordered list.
+    List(Option<usize>),
+    Item,
+    /// Footnote definition, with its label.
+    FootnoteDefinition(&'a str),
+    /// Heading, with its level, ID and other attributes.
+    Heading {
+        level: u8,
+        id: Option<&'a str>,
+        attributes: &'a Attributes,
+    },
+    Paragraph,
+    /// Code block, with its info string and attributes.
+    CodeBlock(Option<&'a str>, &'a Attributes),
+    /// Table, with the alignment of each column.
+    Table(&'a [Alignment]),
+    /// Header row of a table.
+    TableHead,
+    TableRow,
+    TableCell,
+    Emphasis,
+    Strong,
+    Strikethrough,
+    Link {
+        destination: &'a str,
+        title: Option<&'a str>,
+    },
+    Image {
+        destination: &'a str,
+        title: Option<&'a str>,
+    },
+    /// Autolink, with its destination.
+    Autolink(&'a str),
+}
+
+/// Event in a syntax tree.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event<'a> {
+    Start(Tag<'a>),
+    End(Tag<'a>),
+    Text(&'a str),
+    /// Code span.
+    Code(&'a str),
+    /// HTML block.
+    Html(&'a str),
+    InlineHtml(&'a str),
+    /// Footnote reference, with its label.
+    FootnoteReference(&'a str),
+    /// Task list item marker, with whether the task is checked.
+    TaskListMarker(bool),
+    SoftBreak,
+    HardBreak,
+    /// Thematic break.
+    Rule,
+}
+
+/// Pending step of the walk.
+enum Step<'a> {
+    /// Enter a node, emitting its events.
+    Enter(&'a Node),
+    /// Emit an event.
+    Emit(Event<'a>, Position),
+}
+
+/// Iterator over the events of a syntax tree.
+pub struct Events<'a> {
+    /// Steps to take, with the next step on top.
+    stack: Vec<Step<'a>>,
+}
+
+impl<'a> Events<'a> {
+    /// Create an event stream over a node and its descendants.
+    pub fn new(node: &'a Node) -> Self {
+        Self {
+            stack: vec![Step::Enter(node)],
+        }
+    }
+
+    /// Pair each event with the source position of the node it came from.
+    pub fn with_positions(self) -> PositionedEvents<'a> {
+        PositionedEvents(self)
+    }
+
+    /// Returns the next event and its source position.
+    fn next_with_position(&mut self) -> Option<(Event<'a>, Position)> {
+        loop {
+            let node = match self.stack.pop()? {
+                Step::Emit(event, position) => return Some((event, position)),
+                Step::Enter(node) => node,
+            };
+            let position = node.position();
+            let (tag, children) = match node {
+                Node::Root(x) => {
+                    self.enter(&x.children);
+                    continue;
+                }
+                Node::BlockQuote(x) => (Tag::BlockQuote, &x.children),
+                Node::List(x) => {
+                    let start = match x.list_type {
+                        ListType::Unordered(_) => None,
+                        ListType::Ordered(_, start) => Some(start),
+                    };
+                    (Tag::List(start), &x.children)
+                }
+                Node::ListItem(ast::ListItem {
+                    checked: Some(checked),
+                    children,
+                    ..
+                }) => {
+                    // the marker starts the content of the item, which is in
+                    // its first paragraph unless the list is tight
+                    let marker = Step::Emit(Event::TaskListMarker(*checked), position);
+                    self.stack.push(Step::Emit(Event::End(Tag::Item), position));
+                    match children.split_first() {
+                        Some((Node::Paragraph(para), rest)) => {
+                            self.enter(rest);
+                            let position = para.position;
+                            self.stack
+                                .push(Step::Emit(Event::End(Tag::Paragraph), position));
+                            self.enter(&para.children);
+                            self.stack.push(marker);
+                            self.stack
+                                .push(Step::Emit(Event::Start(Tag::Paragraph), position));
+                        }
+                        _ => {
+                            self.enter(children);
+                            self.stack.push(marker);
+                        }
+                    }
+                    return Some((Event::Start(Tag::Item), position));
+                }
+                Node::ListItem(x) => (Tag::Item, &x.children),
+                Node::FootnoteDefinition(x) => (Tag::FootnoteDefinition(&x.label), &x.children),
+                Node::Heading(x) => {
+                    let tag = Tag::Heading {
+                        level: x.level,
+                        id: x.id.as_deref(),
+                        attributes: &x.attributes,
+                    };
+                    (tag, &x.children)
+                }
+                Node::Paragraph(x) => (Tag::Paragraph, &x.children),
+                Node::Table(x) => {
+                    let tag = Tag::Table(&x.alignments);
+                    self.stack
+                        .push(Step::Emit(Event::End(tag.clone()), position));
+                    // the first row is the header row
+                    if let Some((head, body)) = x.children.split_first() {
+                        self.enter(body);
+                        let children = head.children().map_or(&[][..], |c| c);
+                        let position = head.position();
+                        self.stack
+                            .push(Step::Emit(Event::End(Tag::TableHead), position));
+                        self.enter(children);
+                        self.stack
+                            .push(Step::Emit(Event::Start(Tag::TableHead), position));
+                    }
+                    return Some((Event::Start(tag), position));
+                }
+                Node::TableRow(x) => (Tag::TableRow, &x.children),
+                Node::TableCell(x) => (Tag::TableCell, &x.children),
+                Node::Code(x) => {
+                    let tag = Tag::CodeBlock(x.info.as_deref(), &x.attributes);
+                    self.stack
+                        .push(Step::Emit(Event::End(tag.clone()), position));
+                    self.stack.push(Step::Emit(Event::Text(&x.text), position));
+                    return Some((Event::Start(tag), position));
+                }
+                Node::Emphasis(x) => (Tag::Emphasis, &x.children),
+                Node::Strong(x) => (Tag::Strong, &x.children),
+                Node::Strikethrough(x) => (Tag::Strikethrough, &x.children),
+                Node::Link(x) => {
+                    let tag = Tag::Link {
+                        destination: &x.destination,
+                        title: x.title.as_deref(),
+                    };
+                    (tag, &x.children)
+                }
+                Node::Image(x) => {
+                    let tag = Tag::Image {
+                        destination: &x.destination,
+                        title: x.title.as_deref(),
+                    };
+                    (tag, &x.children)
+                }
+                Node::Autolink(x) => {
+                    let tag = Tag::Autolink(&x.destination);
+                    self.stack
+                        .push(Step::Emit(Event::End(tag.clone()), position));
+                    self.stack.push(Step::Emit(Event::Text(&x.text), position));
+                    return Some((Event::Start(tag), position));
+                }
+                // front matter is metadata rather than content
+                Node::FrontMatter(_) => continue,
+                Node::ThematicBreak(_) => return Some((Event::Rule, position)),
+                Node::Html(x) => return Some((Event::Html(&x.text), position)),
+                Node::CodeSpan(x) => return Some((Event::Code(&x.text), position)),
+                Node::InlineHtml(x) => return Some((Event::InlineHtml(&x.text), position)),
+                Node::FootnoteReference(x) => {
+                    return Some((Event::FootnoteReference(&x.label), position))
+                }
+                Node::SoftBreak(_) => return Some((Event::SoftBreak, position)),
+                Node::HardBreak(_) => return Some((Event::HardBreak, position)),
+                Node::Text(x) => return Some((Event::Text(&x.text), position)),
+            };
+            self.stack
+                .push(Step::Emit(Event::End(tag.clone()), position));
+            self.enter(children);
+            return Some((Event::Start(tag), position));
+        }
+    }
+
+    /// Schedule a list of nodes to be entered in order.
+    fn enter(&mut self, children: &'a [Node]) {
+        self.stack.extend(children.iter().rev().map(Step::Enter));
+    }
+}
+
+impl<'a> Iterator for Events<'a> {
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_position().map(|(event, _)| event)
+    }
+}
+
+/// Iterator over the events of a syntax tree and their source positions.
+pub struct PositionedEvents<'a>(Events<'a>);
+
+impl<'a> Iterator for PositionedEvents<'a> {
+    type Item = (Event<'a>, Position);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next_with_position()
+    }
+}
@@ -0,0 +1,616 @@
+//! HTML rendering.
+//!
+//! The renderer consumes the [`Event`] stream of a syntax tree, so any
+//! renderer built on [`Events`](crate::event::Events) sees the same document.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    ast::{Alignment, Attributes, Position},
+    event::{Event, Tag},
+    inline::normalize_label,
+    Options, SoftBreak,
+};
+
+/// Write the HTML for a stream of events and their source positions.
+///
+/// Source positions are only used for `data-sourcepos` attributes, so events
+/// from other sources can be paired with [`Position::default`] when those are
+/// not rendered.
+///
+/// Footnote definitions are rendered in a footnotes section after the rest of
+/// the events, in the order in which they are first referenced. Definitions
+/// that are not referenced are omitted.
+///
+/// # Examples
+///
+/// ```
+/// use markdown::{event::Events, html};
+///
+/// let root = markdown::parse("hello *world*");
+/// let events = Events::new(&root).with_positions();
+/// let mut output = String::new();
+/// html::write_html(&mut output, events, &Default::default()).unwrap();
+/// assert_eq!(output, "<p>hello <em>world</em></p>\n");
+/// ```
+pub fn write_html<'a>(
+    f: &mut dyn Write,
+    events: impl IntoIterator<Item = (Event<'a>, Position)>,
+    options: &Options,
+) -> fmt::Result {
+    let mut writer = HtmlWriter {
+        f,
+        options,
+        newline: true,
+        image_depth: 0,
+        table: TableState::default(),
+        tasks: 0,
+        footnotes: FootnoteState::default(),
+    };
+    for (event, position) in events {
+        writer.write_event(event, position)?;
+    }
+    writer.write_footnotes()
+}
+
+/// HTML writer.
+///
+/// Used to track rendering state.
+struct HtmlWriter<'a, 'e> {
+    /// Output.
+    f: &'a mut dyn Write,
+    /// Rendering options.
+    options: &'a Options,
+    /// Whether the output is at the start of a line.
+    newline: bool,
+    /// The number of images being rendered, whose content is rendered as
+    /// plain alt text.
+    image_depth: usize,
+    /// The table being rendered.
+    table: TableState,
+    /// The number of task list items rendered so far.
+    tasks: usize,
+    /// The footnotes referenced and defined so far.
+    footnotes: FootnoteState<'e>,
+}
+
+/// Table rendering state.
+#[derive(Default)]
+struct TableState {
+    /// The alignment of each column.
+    alignments: Vec<Alignment>,
+    /// The index of the next cell in the current row.
+    column: usize,
+    /// Whether the header row is being rendered.
+    head: bool,
+    /// Whether the table body has been opened.
+    body: bool,
+}
+
+/// Footnote rendering state.
+#[derive(Default)]
+struct FootnoteState<'e> {
+    /// The referenced footnotes, in the order in which they are first
+    /// referenced.
+    references: Vec<Footnote>,
+    /// The events of the footnote definitions, keyed by normalized label.
+    definitions: HashMap<String, Definition<'e>>,
+    /// The footnote definition whose events are being collected.
+    open: Option<(String, Definition<'e>)>,
+    /// The output of a footnote definition being rendered.
+    capture: Option<String>,
+}
+
+/// Referenced footnote.
+struct Footnote {
+    /// The normalized label.
+    key: String,
+    /// The label of the first reference, used in element IDs.
+    label: String,
+    /// The number of references.
+    count: usize,
+}
+
+/// Footnote definition, whose events are held back until the footnotes
+/// section.
+#[derive(Default)]
+struct Definition<'e> {
+    /// The `data-sourcepos` attribute of the definition.
+    pos: String,
+    /// The events of the content of the definition.
+    events: Vec<(Event<'e>, Position)>,
+    /// The number of nested definitions that are open.
+    depth: usize,
+}
+
+impl<'a, 'e> HtmlWriter<'a, 'e> {
+    /// Write a string to the output, or to the footnote definition being
+    /// rendered.
+    fn write(&mut self, s: &str) -> fmt::Result {
+        if !s.is_empty() {
+            self.newline = s.ends_with('\n');
+            match &mut self.footnotes.capture {
+                Some(capture) => capture.push_str(s),
+                None => self.f.write_str(s)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Start a new line if the output is not already at the start of one.
+    /// Block elements always start on a new line.
+    fn block(&mut self) -> fmt::Result {
+        match self.newline {
+            true => Ok(()),
+            false => self.write("\n"),
+        }
+    }
+
+    /// Write a single event.
+    fn write_event(&mut self, event: Event<'e>, position: Position) -> fmt::Result {
+        if self.footnotes.open.is_some() {
+            self.collect_footnote_event(event, position);
+            return Ok(());
+        }
+        if self.image_depth > 0 {
+            return self.write_alt(event);
+        }
+        let pos = match self.options.source_positions {
+            true => sourcepos(position),
+            false => "".to_string(),
+        };
+        match event {
+            Event::Start(Tag::FootnoteDefinition(label)) => {
+                let definition = Definition {
+                    pos,
+                    ..Default::default()
+                };
+                self.footnotes.open = Some((normalize_label(label), definition));
+                Ok(())
+            }
+            Event::Start(tag) => self.start(tag, &pos),
+            Event::End(tag) => self.end(tag),
+            Event::Text(text) => self.write(&escape(text)),
+            Event::Code(text) => self.write(&format!("<code>{}</code>", escape(text))),
+            Event::Html(text) => {
+                self.block()?;
+                self.write_raw_html(text, "\n")
+            }
+            Event::InlineHtml(text) => self.write_raw_html(text, ""),
+            Event::FootnoteReference(label) => self.write_footnote_reference(label),
+            Event::TaskListMarker(checked) => {
+                let checked = if checked { r#"checked="" "# } else { "" };
+                let input = match self.options.interactive_tasklist {
+                    true => format!(r#"type="checkbox" data-task-index="{}""#, self.tasks),
+                    false => r#"disabled="" type="checkbox""#.to_string(),
+                };
+                self.tasks += 1;
+                self.write(&format!("<input {checked}{input}> "))
+            }
+            Event::SoftBreak => match self.options.soft_break {
+                SoftBreak::Newline => self.write("\n"),
+                SoftBreak::Space => self.write(" "),
+                SoftBreak::Break => self.write("<br />\n"),
+            },
+            Event::HardBreak => self.write("<br />\n"),
+            Event::Rule => {
+                self.block()?;
+                self.write(&format!("<hr{pos} />\n"))
+            }
+        }
+    }
+
+    /// Collect an event of the footnote definition that is open, closing the
+    /// definition at its end.
+    fn collect_footnote_event(&mut self, event: Event<'e>, position: Position) {
+        let Some((key, mut definition)) = self.footnotes.open.take() else {
+            return;
+        };
+        match event {
+            Event::Start(Tag::FootnoteDefinition(_)) => definition.depth += 1,
+            Event::End(Tag::FootnoteDefinition(_)) if definition.depth == 0 => {
+                // the first definition of a label is used
+                self.footnotes.definitions.entry(key).or_insert(definition);
+                return;
+            }
+            Event::End(Tag::FootnoteDefinition(_)) => definition.depth -= 1,
+            _ => {}
+        }
+        definition.events.push((event, position));
+        self.footnotes.open = Some((key, definition));
+    }
+
+    /// Write the opening of a tag.
+    fn start(&mut self, tag: Tag, pos: &str) -> fmt::Result {
+        match tag {
+            Tag::BlockQuote => {
+                self.block()?;
+                self.write(&format!("<blockquote{pos}>\n"))
+            }
+            Tag::List(start) => {
+                self.block()?;
+                match start {
+                    None => self.write(&format!("<ul{pos}>\n")),
+                    Some(1) => self.write(&format!("<ol{pos}>\n")),
+                    Some(start) => self.write(&format!("<ol start=\"{start}\"{pos}>\n")),
+                }
+            }
+            Tag::Item => {
+                self.block()?;
+                self.write(&format!("<li{pos}>"))
+            }
+            // footnote definitions are collected instead
+            Tag::FootnoteDefinition(_) => Ok(()),
+            Tag::Heading {
+                level,
+                id,
+                attributes,
+            } => {
+                self.block()?;
+                let attrs = attributes_html(id, vec![], attributes, self.options);
+                self.write(&format!("<h{level}{attrs}{pos}>"))?;
+                match (id, self.options.heading_anchors) {
+                    (Some(id), true) => {
+                        let href = escape(&encode_url(id));
+                        self.write(&format!(
+                            r##"<a class="anchor" href="#{href}" aria-hidden="true"></a>"##
+                        ))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Tag::Paragraph => {
+                self.block()?;
+                self.write(&format!("<p{pos}>"))
+            }
+            Tag::CodeBlock(info, attributes) => {
+                self.block()?;
+                let language = info.map(|info| {
+                    let i = info.trim().split(' ').next().unwrap_or_default();
+                    format!("language-{i}")
+                });
+                let id = attributes.id.as_deref();
+                let attrs =
+                    attributes_html(id, language.into_iter().collect(), attributes, self.options);
+                self.write(&format!("<pre{pos}><code{attrs}>"))
+            }
+            Tag::Table(alignments) => {
+                self.block()?;
+                self.table = TableState {
+                    alignments: alignments.to_vec(),
+                    ..Default::default()
+                };
+                self.write(&format!("<table{pos}>\n"))
+            }
+            Tag::TableHead => {
+                self.table.head = true;
+                self.table.column = 0;
+                self.write(&format!("<thead>\n<tr{pos}>\n"))
+            }
+            Tag::TableRow => {
+                if !self.table.body {
+                    self.table.body = true;
+                    self.write("<tbody>\n")?;
+                }
+                self.table.column = 0;
+                self.write(&format!("<tr{pos}>\n"))
+            }
+            Tag::TableCell => {
+                let cell = if self.table.head { "th" } else { "td" };
+                let align = match self.table.alignments.get(self.table.column) {
+                    Some(Alignment::Left) => r#" align="left""#,
+                    Some(Alignment::Center) => r#" align="center""#,
+                    Some(Alignment::Right) => r#" align="right""#,
+                    Some(Alignment::None) | None => "",
+                };
+                self.table.column += 1;
+                self.write(&format!("<{cell}{align}{pos}>"))
+            }
+            Tag::Emphasis => self.write("<em>"),
+            Tag::Strong => self.write("<strong>"),
+            Tag::Strikethrough => self.write("<del>"),
+            Tag::Link { destination, title } => {
+                let href = self.url(destination, false);
+                let title = match title {
+                    Some(title) => format!(r#" title="{}""#, escape(title)),
+                    None => "".to_string(),
+                };
+                self.write(&format!(r#"<a href="{href}"{title}>"#))
+            }
+            Tag::Image { destination, .. } => {
+                self.image_depth += 1;
+                let src = self.url(destination, true);
+                self.write(&format!(r#"<img src="{src}" alt=""#))
+            }
+            Tag::Autolink(destination) => {
+                let href = self.url(destination, false);
+                self.write(&format!(r#"<a href="{href}">"#))
+            }
+        }
+    }
+
+    /// Write the closing of a tag.
+    fn end(&mut self, tag: Tag) -> fmt::Result {
+        match tag {
+            Tag::BlockQuote => self.write("</blockquote>\n"),
+            Tag::List(None) => self.write("</ul>\n"),
+            Tag::List(Some(_)) => self.write("</ol>\n"),
+            Tag::Item => self.write("</li>\n"),
+            Tag::FootnoteDefinition(_) => Ok(()),
+            Tag::Heading { level, .. } => self.write(&format!("</h{level}>\n")),
+            Tag::Paragraph => self.write("</p>\n"),
+            Tag::CodeBlock(..) => self.write("</code></pre>\n"),
+            Tag::Table(_) => match self.table.body {
+                true => self.write("</tbody>\n</table>\n"),
+                false => self.write("</table>\n"),
+            },
+            Tag::TableHead => {
+                self.table.head = false;
+                self.write("</tr>\n</thead>\n")
+            }
+            Tag::TableRow => self.write("</tr>\n"),
+            Tag::TableCell => match self.table.head {
+                true => self.write("</th>\n"),
+                false => self.write("</td>\n"),
+            },
+            Tag::Emphasis => self.write("</em>"),
+            Tag::Strong => self.write("</strong>"),
+            Tag::Strikethrough => self.write("</del>"),
+            Tag::Link { .. } | Tag::Autolink(_) => self.write("</a>"),
+            // images are closed while writing alt text
+            Tag::Image { .. } => Ok(()),
+        }
+    }
+
+    /// Write a footnote reference, numbering the footnote if it is the first
+    /// reference to it.
+    fn write_footnote_reference(&mut self, label: &str) -> fmt::Result {
+        let key = normalize_label(label);
+        let references = &mut self.footnotes.references;
+        let index = match references.iter().position(|f| f.key == key) {
+            Some(index) => index,
+            None => {
+                references.push(Footnote {
+                    key,
+                    label: label.to_string(),
+                    count: 0,
+                });
+                references.len() - 1
+            }
+        };
+        let footnote = &mut references[index];
+        footnote.count += 1;
+        let id = match footnote.count {
+            1 => escape(&footnote.label),
+            n => format!("{}-{n}", escape(&footnote.label)),
+        };
+        let href = escape(&encode_url(&footnote.label));
+        let number = index + 1;
+        self.write(&format!(
+            r##"<sup class="footnote-ref"><a href="#fn-{href}" id="fnref-{id}" data-footnote-ref>{number}</a></sup>"##
+        ))
+    }
+
+    /// Write the footnotes section with the referenced footnote definitions
+    /// and links back to their references.
+    fn write_footnotes(&mut self) -> fmt::Result {
+        // definitions may reference footnotes that are not referenced yet,
+        // so the definitions are rendered before the links back are known
+        let mut items = vec![];
+        let mut index = 0;
+        while let Some(footnote) = self.footnotes.references.get(index) {
+            if let Some(definition) = self.footnotes.definitions.remove(&footnote.key) {
+                self.footnotes.capture = Some(String::new());
+                self.newline = true;
+                for (event, position) in definition.events {
+                    self.write_event(event, position)?;
+                }
+                let html = self.footnotes.capture.take().unwrap_or_default();
+                items.push((index, definition.pos, html));
+            }
+            index += 1;
+        }
+        if items.is_empty() {
+            return Ok(());
+        }
+        self.block()?;
+        self.write("<section class=\"footnotes\" data-footnotes>\n<ol>\n")?;
+        for (index, pos, html) in items {
+            let footnote = &self.footnotes.references[index];
+            let number = index + 1;
+            let href = escape(&encode_url(&footnote.label));
+            let backrefs: Vec<String> = (1..=footnote.count)
+                .map(|n| {
+                    let (suffix, sup) = match n {
+                        1 => ("".to_string(), "".to_string()),
+                        n => (
+                            format!("-{n}"),
+                            format!(r#"<sup class="footnote-ref">{n}</sup>"#),
+                        ),
+                    };
+                    format!(
+                        r##"<a href="#fnref-{href}{suffix}" class="footnote-backref" data-footnote-backref data-footnote-backref-idx="{number}{suffix}" aria-label="Back to reference {number}{suffix}">↩{sup}</a>"##
+                    )
+                })
+                .collect();
+            let backrefs = backrefs.join(" ");
+            // the links back go at the end of the last paragraph, if there
+            // is one
+            let html = match html.strip_suffix("</p>\n") {
+                Some(html) => format!("{html} {backrefs}</p>\n"),
+                None => format!("{html}{backrefs}\n"),
+            };
+            let id = escape(&footnote.label);
+            self.write(&format!("<li id=\"fn-{id}\"{pos}>\n{html}</li>\n"))?;
+        }
+        self.write("</ol>\n</section>\n")
+    }
+
+    /// Write raw HTML, unless unsafe HTML is disabled, in which case it is
+    /// escaped or replaced with a comment followed by the given line ending.
+    fn write_raw_html(&mut self, text: &str, ending: &str) -> fmt::Result {
+        match (self.options.unsafe_html, self.options.escape_html) {
+            (true, _) if self.options.tagfilter => {
+                self.write(&DISALLOWED_TAG_RE.replace_all(text, "&lt;$1"))
+            }
+            (true, _) => self.write(text),
+            (false, true) => self.write(&escape(text)),
+            (false, false) => self.write(&format!("<!-- raw HTML omitted -->{ending}")),
+        }
+    }
+
+    /// Returns the encoded and escaped form of a link or image URL. Unsafe
+    /// URLs are replaced with an empty string unless unsafe HTML is enabled.
+    fn url(&self, url: &str, image: bool) -> String {
+        if !self.options.unsafe_html && is_dangerous_url(url, image) {
+            return "".to_string();
+        }
+        escape(&encode_url(url))
+    }
+
+    /// Write an event inside an image as plain alt text.
+    fn write_alt(&mut self, event: Event) -> fmt::Result {
+        match event {
+            Event::Start(Tag::Image { .. }) => {
+                self.image_depth += 1;
+                Ok(())
+            }
+            Event::End(Tag::Image { title, .. }) => {
+                self.image_depth -= 1;
+                if self.image_depth > 0 {
+                    return Ok(());
+                }
+                let title = match title {
+                    Some(title) => format!(r#" title="{}""#, escape(title)),
+                    None => "".to_string(),
+                };
+                self.write(&format!(r#""{title} />"#))
+            }
+            Event::Text(text) | Event::Code(text) | Event::InlineHtml(text) => {
+                self.write(&escape(text))
+            }
+            Event::SoftBreak | Event::HardBreak => self.write("\n"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Returns a `data-sourcepos` attribute for a position, using inclusive end
+/// columns like cmark.
+fn sourcepos(position: Position) -> String {
+    let Position { start, end } = position;
+    format!(
+        r#" data-sourcepos="{}:{}-{}:{}""#,
+        start.line,
+        start.column,
+        end.line,
+        end.column.saturating_sub(1)
+    )
+}
+
+lazy_static! {
+    // tags that GFM disallows in raw HTML, followed by whitespace, the end of
+    // the tag or the end of the text
+    static ref DISALLOWED_TAG_RE: Regex = Regex::new(
+        r"(?xi)
+        <
+        (
+            /?
+            (?:title|textarea|style|xmp|iframe|noembed|noframes|script|plaintext)
+            (?:\s|>|/>|$)
+        )
+        "
+    )
+    .expect("disallowed tag regex should be valid");
+}
+
+/// Image types that are allowed in `data:` URLs of images.
+const SAFE_DATA_IMAGES: [&str; 4] = ["png", "gif", "jpeg", "webp"];
+
+/// Returns whether a URL uses a scheme that can run code or read local files:
+/// `javascript:`, `vbscript:`, `file:`, or `data:` other than for images.
+fn is_dangerous_url(url: &str, image: bool) -> bool {
+    let url = url.trim_start().to_ascii_lowercase();
+    if let Some(data) = url.strip_prefix("data:") {
+        let safe_image = SAFE_DATA_IMAGES
+            .iter()
+            .any(|kind| data.starts_with(&format!("image/{kind}")));
+        return !(image && safe_image);
+    }
+    ["javascript:", "vbscript:", "file:"]
+        .iter()
+        .any(|scheme| url.starts_with(scheme))
+}
+
+const ESCAPES: [(&str, &str); 4] = [
+    ("&", "&amp;"),
+    ("<", "&lt;"),
+    (">", "&gt;"),
+    ("\"", "&quot;"),
+];
+
+/// Escape the characters that are significant in HTML text and attributes.
+fn escape(input: &str) -> String {
+    let mut output = input.to_string();
+    for (from, to) in ESCAPES {
+        output = output.replace(from, to);
+    }
+    output
+}
+
+/// Returns the HTML attributes for an element with an ID, classes to put
+/// before the classes of an attribute block, and the other attributes of the
+/// block. Event handler attributes, such as `onclick`, are omitted unless
+/// unsafe HTML is rendered.
+fn attributes_html(
+    id: Option<&str>,
+    mut classes: Vec<String>,
+    attributes: &Attributes,
+    options: &Options,
+) -> String {
+    let mut output = String::new();
+    if let Some(id) = id {
+        output.push_str(&format!(r#" id="{}""#, escape(id)));
+    }
+    classes.extend(attributes.classes.iter().cloned());
+    if !classes.is_empty() {
+        output.push_str(&format!(r#" class="{}""#, escape(&classes.join(" "))));
+    }
+    for (name, value) in &attributes.attrs {
+        let handler = name.to_ascii_lowercase().starts_with("on");
+        if !handler || options.unsafe_html {
+            output.push_str(&format!(r#" {name}="{}""#, escape(value)));
+        }
+    }
+    output
+}
+
+/// Characters that are left as-is when percent-encoding a URL.
+const URL_SAFE: &str = ";/?:@&=+$,-_.!~*'()#";
+
+/// Percent-encode the characters in a URL that are not allowed in links,
+/// leaving existing percent-encoded sequences intact.
+fn encode_url(input: &str) -> String {
+    let mut output = String::new();
+    for (i, ch) in input.char_indices() {
+        let escaped = ch == '%'
+            && input.len() >= i + 3
+            && input.as_bytes()[i + 1..i + 3]
+                .iter()
+                .all(|b| b.is_ascii_hexdigit());
+        if ch.is_ascii_alphanumeric() || URL_SAFE.contains(ch) || escaped {
+            output.push(ch);
+        } else {
+            for byte in ch.to_string().bytes() {
+                output.push_str(&format!("%{byte:02X}"));
+            }
+        }
+    }
+    output
+}
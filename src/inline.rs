@@ -0,0 +1,1193 @@
+//! Inline parsing.
+
+use std::collections::{HashMap, HashSet};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::{
+    ast::{
+        self, Emphasis, LineBreak,
+        Node::{self, *},
+        Point, Position, Strikethrough, Strong,
+    },
+    Options, HTML_TAG,
+};
+
+lazy_static! {
+    static ref PUNCTUATION_RE: Regex =
+        Regex::new(r"^\p{P}$").expect("punctuation regex should be valid");
+    static ref CHARACTER_REFERENCE_RE: Regex = Regex::new(
+        r"(?x)
+        ^&
+        (?:
+            # entity
+            ([[:alpha:]][[:alnum:]]{0,31})
+            # decimal
+            |\#([0-9]{1,7})
+            # hexadecimal
+            |\#[xX]([[:xdigit:]]{1,6})
+        )
+        ;
+        "
+    )
+    .expect("character reference regex should be valid");
+    static ref URI_AUTOLINK_RE: Regex = Regex::new(
+        r"(?x)
+        ^<
+        # scheme
+        ([[:alpha:]][[:alnum:]+.\-]{1,31}:
+        # rest
+        [^<>\x00-\x20]*)
+        >
+        "
+    )
+    .expect("uri autolink regex should be valid");
+    static ref EMAIL_AUTOLINK_RE: Regex = Regex::new(
+        r"(?x)
+        ^<
+        # local part
+        ([[:alnum:].!\#$%&'*+/=?^_`{|}~\-]+
+        @
+        # domain
+        [[:alnum:]](?:[[:alnum:]\-]{0,61}[[:alnum:]])?
+        (?:\.[[:alnum:]](?:[[:alnum:]\-]{0,61}[[:alnum:]])?)*)
+        >
+        "
+    )
+    .expect("email autolink regex should be valid");
+    static ref INLINE_HTML_RE: Regex = Regex::new(&format!(
+        r"(?xi)
+        ^(?:
+            # tag
+            {HTML_TAG}
+            # comment
+            |<!---->
+            |<!--(?:-?[^>\-])(?:-?[^\-])*-->
+            # processing instruction
+            |<\?(?s:.)*?\?>
+            # declaration
+            |<![[:alpha:]]+[^>]*>
+            # cdata
+            |<!\[CDATA\[(?s:.)*?\]\]>
+        )
+        "
+    ))
+    .expect("inline html regex should be valid");
+    /// Named HTML entities, keyed by name without delimiters.
+    static ref ENTITIES: HashMap<&'static str, &'static str> = entities::ENTITIES
+        .iter()
+        .filter_map(|e| {
+            let name = e.entity.strip_prefix('&')?.strip_suffix(';')?;
+            Some((name, e.characters))
+        })
+        .collect();
+}
+
+/// Delimiter run.
+///
+/// Tracks a run of `*`, `_` or `~` characters that may open or close emphasis
+/// or strikethrough.
+struct Delimiter {
+    /// The delimiter character.
+    ch: char,
+    /// The index of the text node holding the run.
+    index: usize,
+    /// The number of delimiters remaining in the run.
+    count: usize,
+    /// The number of delimiters originally in the run.
+    length: usize,
+    /// Whether the run can open emphasis.
+    can_open: bool,
+    /// Whether the run can close emphasis.
+    can_close: bool,
+}
+
+/// Link reference definition.
+#[derive(Clone)]
+pub struct Definition {
+    pub destination: String,
+    pub title: Option<String>,
+}
+
+/// Link reference and footnote definitions.
+#[derive(Default)]
+pub struct Definitions {
+    /// Link reference definitions, keyed by normalized label.
+    pub links: HashMap<String, Definition>,
+    /// Normalized labels of footnote definitions.
+    pub footnotes: HashSet<String>,
+}
+
+impl Definitions {
+    /// Create an empty set of definitions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Bracket.
+///
+/// Tracks a `[` or `![` that may open a link or image.
+struct Bracket {
+    /// The index of the text node holding the bracket.
+    index: usize,
+    /// Whether the bracket opens an image.
+    image: bool,
+    /// Whether the bracket can still open a link.
+    active: bool,
+    /// The size of the delimiter stack when the bracket was pushed.
+    bottom: usize,
+    /// The position in the input after the bracket.
+    position: usize,
+    /// Whether another bracket was pushed after this one.
+    bracket_after: bool,
+}
+
+/// Map from byte offsets in the inline input to points in the source text.
+///
+/// Each line of the input is contiguous in the source, so the map records
+/// where each line starts.
+pub struct SourceMap {
+    lines: Vec<(usize, Point)>,
+}
+
+impl SourceMap {
+    /// Create an empty source map.
+    pub fn new() -> Self {
+        Self { lines: vec![] }
+    }
+
+    /// Record that the line at an offset in the input starts at a point.
+    pub fn push(&mut self, offset: usize, point: Point) {
+        self.lines.push((offset, point));
+    }
+
+    /// Returns the source point for an offset in the input.
+    pub fn point(&self, offset: usize) -> Point {
+        match self.lines.partition_point(|(start, _)| *start <= offset) {
+            0 => Point::default(),
+            i => {
+                let (start, point) = self.lines[i - 1];
+                point.add(offset - start)
+            }
+        }
+    }
+}
+
+/// Inline parser.
+///
+/// Used to track parsing state.
+struct Parser<'a> {
+    /// Input text.
+    input: &'a str,
+    /// Current position in the input.
+    position: usize,
+    /// Parsed nodes.
+    nodes: Vec<Node>,
+    /// Text that has not yet been pushed as a node.
+    text: String,
+    /// The position in the input where the pending text starts.
+    text_start: usize,
+    /// The delimiter stack.
+    delimiters: Vec<Delimiter>,
+    /// The bracket stack.
+    brackets: Vec<Bracket>,
+    /// Link reference and footnote definitions in the document.
+    definitions: &'a Definitions,
+    /// Source positions of the input.
+    map: &'a SourceMap,
+    /// Parsing options.
+    options: &'a Options,
+}
+
+/// Parses the inline content of a block into nodes.
+pub fn parse(
+    input: &str,
+    definitions: &Definitions,
+    map: &SourceMap,
+    options: &Options,
+) -> Vec<Node> {
+    let mut parser = Parser::new(input, definitions, map, options);
+    while let Some(ch) = parser.peek() {
+        if parser.text.is_empty() {
+            parser.text_start = parser.position;
+        }
+        match ch {
+            '\\' => parser.parse_backslash(),
+            '&' => parser.parse_ampersand(),
+            '`' => parser.parse_code_span(),
+            '<' => parser.parse_angle_bracket(),
+            '*' | '_' => parser.parse_delimiter_run(ch),
+            '~' if parser.options.strikethrough => parser.parse_delimiter_run(ch),
+            '[' if parser.options.footnotes
+                && parser.input[parser.position..].starts_with("[^") =>
+            {
+                parser.parse_footnote_reference()
+            }
+            '[' => parser.parse_open_bracket(),
+            '!' if parser.input[parser.position + 1..].starts_with('[') => {
+                parser.parse_open_bracket()
+            }
+            ']' => parser.parse_close_bracket(),
+            '\n' => parser.parse_newline(),
+            _ => {
+                parser.text.push(ch);
+                parser.position += ch.len_utf8();
+            }
+        }
+    }
+    parser.flush();
+    parser.process_emphasis(0);
+    let nodes = normalize(parser.nodes);
+    match options.autolink {
+        true => extended_autolinks(nodes),
+        false => nodes,
+    }
+}
+
+impl<'a> Parser<'a> {
+    /// Create a new parser.
+    fn new(
+        input: &'a str,
+        definitions: &'a Definitions,
+        map: &'a SourceMap,
+        options: &'a Options,
+    ) -> Self {
+        Self {
+            input,
+            position: 0,
+            nodes: vec![],
+            text: String::new(),
+            text_start: 0,
+            delimiters: vec![],
+            brackets: vec![],
+            definitions,
+            map,
+            options,
+        }
+    }
+
+    /// Returns the source position of a span of the input.
+    fn span(&self, start: usize, end: usize) -> Position {
+        Position::new(self.map.point(start), self.map.point(end))
+    }
+
+    /// Push any pending text, then a node spanning from the start offset to
+    /// the current position.
+    fn push(&mut self, node: Node, start: usize) {
+        self.flush_until(start);
+        let position = self.span(start, self.position);
+        self.nodes.push(node.with_position(position));
+    }
+
+    /// Returns the next character in the input.
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    /// Push any pending text as a node.
+    fn flush(&mut self) {
+        self.flush_until(self.position);
+    }
+
+    /// Push any pending text as a node ending at the given offset.
+    fn flush_until(&mut self, end: usize) {
+        if !self.text.is_empty() {
+            let text = std::mem::take(&mut self.text);
+            let position = self.span(self.text_start, end);
+            self.nodes
+                .push(Text(ast::Text::new(text)).with_position(position));
+        }
+    }
+
+    /// Parse a backslash, which escapes the following punctuation character
+    /// or line ending.
+    fn parse_backslash(&mut self) {
+        let start = self.position;
+        self.position += 1;
+        match self.peek() {
+            Some('\n') => {
+                self.position += 1;
+                self.push_break(HardBreak(LineBreak::new()), start);
+            }
+            Some(ch) if ch.is_ascii_punctuation() => {
+                self.text.push(ch);
+                self.position += 1;
+            }
+            _ => self.text.push('\\'),
+        }
+    }
+
+    /// Parse an ampersand, which may begin a character reference.
+    fn parse_ampersand(&mut self) {
+        match scan_character_reference(&self.input[self.position..]) {
+            Some((text, len)) => {
+                self.text.push_str(&text);
+                self.position += len;
+            }
+            None => {
+                self.text.push('&');
+                self.position += 1;
+            }
+        }
+    }
+
+    /// Parse a line ending, which is a hard line break if preceded by two or
+    /// more spaces and a soft line break otherwise.
+    fn parse_newline(&mut self) {
+        // only literal spaces count, not ones decoded from references
+        let raw = &self.input[self.text_start..self.position];
+        let spaces = raw.len() - raw.trim_end_matches(' ').len();
+        let node = if spaces >= 2 {
+            HardBreak(LineBreak::new())
+        } else {
+            SoftBreak(LineBreak::new())
+        };
+        self.text.truncate(self.text.len() - spaces);
+        let start = self.position - spaces;
+        self.position += 1;
+        self.push_break(node, start);
+    }
+
+    /// Push a line break, skipping any spaces at the start of the next line.
+    fn push_break(&mut self, node: Node, start: usize) {
+        self.push(node, start);
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start_matches(' ').len();
+    }
+
+    /// Parse a code span, or a literal run of backticks if the run is not
+    /// closed by a run of the same length.
+    fn parse_code_span(&mut self) {
+        let start = self.position;
+        let length = self.input[start..]
+            .chars()
+            .take_while(|c| *c == '`')
+            .count();
+        let content_start = start + length;
+
+        let mut position = content_start;
+        while let Some(offset) = self.input[position..].find('`') {
+            let run_start = position + offset;
+            let run = self.input[run_start..]
+                .chars()
+                .take_while(|c| *c == '`')
+                .count();
+            if run == length {
+                let content = self.input[content_start..run_start].replace('\n', " ");
+                let content = match content.strip_prefix(' ').and_then(|c| c.strip_suffix(' ')) {
+                    Some(stripped) if !content.chars().all(|c| c == ' ') => stripped.to_string(),
+                    _ => content,
+                };
+                self.position = run_start + run;
+                self.push(CodeSpan(ast::CodeSpan::new(content)), start);
+                return;
+            }
+            position = run_start + run;
+        }
+
+        self.text.push_str(&self.input[start..content_start]);
+        self.position = content_start;
+    }
+
+    /// Parse a `<`, which may start an autolink or inline HTML.
+    fn parse_angle_bracket(&mut self) {
+        let start = self.position;
+        let input = &self.input[start..];
+        let (node, len) = if let Some(cap) = URI_AUTOLINK_RE.captures(input) {
+            let text = cap[1].to_string();
+            (
+                Autolink(ast::Autolink::new(text.clone(), text)),
+                cap[0].len(),
+            )
+        } else if let Some(cap) = EMAIL_AUTOLINK_RE.captures(input) {
+            let destination = format!("mailto:{}", &cap[1]);
+            let text = cap[1].to_string();
+            (
+                Autolink(ast::Autolink::new(destination, text)),
+                cap[0].len(),
+            )
+        } else if let Some(mat) = INLINE_HTML_RE.find(input) {
+            let html = ast::InlineHtml::new(mat.as_str().to_string());
+            (InlineHtml(html), mat.len())
+        } else {
+            self.text.push('<');
+            self.position += 1;
+            return;
+        };
+        self.position += len;
+        self.push(node, start);
+    }
+
+    /// Parse a `[` or `![` and push it onto the bracket stack.
+    fn parse_open_bracket(&mut self) {
+        let image = self.input[self.position..].starts_with('!');
+        let delim = if image { "![" } else { "[" };
+        let start = self.position;
+        self.position += delim.len();
+        self.push(Text(ast::Text::new(delim.to_string())), start);
+        if let Some(bracket) = self.brackets.last_mut() {
+            bracket.bracket_after = true;
+        }
+        self.brackets.push(Bracket {
+            index: self.nodes.len() - 1,
+            image,
+            active: true,
+            bottom: self.delimiters.len(),
+            position: self.position,
+            bracket_after: false,
+        });
+    }
+
+    /// Parse a `[^`, which starts a footnote reference if it is followed by
+    /// the label of a footnote definition and a `]`.
+    fn parse_footnote_reference(&mut self) {
+        let start = self.position;
+        let label = self.input[start + 2..]
+            .find(|c: char| c == ']' || c.is_whitespace())
+            .map(|len| &self.input[start + 2..start + 2 + len])
+            .filter(|label| self.input[start + 2 + label.len()..].starts_with(']'));
+        match label {
+            Some(label)
+                if !label.is_empty()
+                    && self.definitions.footnotes.contains(&normalize_label(label)) =>
+            {
+                let reference = ast::FootnoteReference::new(label.to_string());
+                self.position += label.len() + 3;
+                self.push(FootnoteReference(reference), start);
+            }
+            _ => self.parse_open_bracket(),
+        }
+    }
+
+    /// Parse a `]`, which closes a link or image if it matches an opening
+    /// bracket and is followed by a destination or a matching reference.
+    fn parse_close_bracket(&mut self) {
+        let input = self.input;
+        let end = self.position;
+        let text = &input[..end];
+        self.position += 1;
+        let bracket = match self.brackets.pop() {
+            Some(bracket) if bracket.active => bracket,
+            _ => {
+                self.text.push(']');
+                return;
+            }
+        };
+        let text = &text[bracket.position..];
+
+        let (destination, title) = match scan_inline_link(&self.input[self.position..]) {
+            Some((destination, title, len)) => {
+                self.position += len;
+                (destination, title)
+            }
+            None => match self.scan_reference(text, bracket.bracket_after) {
+                Some(definition) => (definition.destination, definition.title),
+                None => {
+                    self.text.push(']');
+                    return;
+                }
+            },
+        };
+
+        self.flush_until(end);
+        self.process_emphasis(bracket.bottom);
+        let children: Vec<Node> = self.nodes.drain(bracket.index + 1..).collect();
+        let opener = self.nodes.pop().expect("bracket should have a text node");
+        let position = Position::new(opener.position().start, self.map.point(self.position));
+        if bracket.image {
+            let image = ast::Image::new(destination, title, children);
+            self.nodes.push(Image(image).with_position(position));
+        } else {
+            let link = ast::Link::new(destination, title, children);
+            self.nodes.push(Link(link).with_position(position));
+            // links may not contain other links
+            for bracket in &mut self.brackets {
+                if !bracket.image {
+                    bracket.active = false;
+                }
+            }
+        }
+    }
+
+    /// Scan a full, collapsed, or shortcut reference following the link text
+    /// and look up its definition.
+    fn scan_reference(&mut self, text: &str, bracket_after: bool) -> Option<Definition> {
+        let input = self.input;
+        let (label, len) = match scan_link_label(&input[self.position..]) {
+            Some((label, len)) if !label.is_empty() => (label, len),
+            // the link text is used as the label for collapsed and shortcut
+            // references, provided it does not contain brackets
+            Some((_, len)) if !bracket_after => (text, len),
+            None if !bracket_after => (text, 0),
+            _ => return None,
+        };
+        let definition = self.definitions.links.get(&normalize_label(label))?.clone();
+        self.position += len;
+        Some(definition)
+    }
+
+    /// Parse a run of `*`, `_` or `~` characters and push it onto the
+    /// delimiter stack.
+    fn parse_delimiter_run(&mut self, ch: char) {
+        let start = self.position;
+        let length = self.input[start..].chars().take_while(|c| *c == ch).count();
+        let end = start + length;
+
+        // strikethrough takes one or two tildes
+        if ch == '~' && length > 2 {
+            self.text.push_str(&self.input[start..end]);
+            self.position = end;
+            return;
+        }
+
+        let before = self.input[..start].chars().last();
+        let after = self.input[end..].chars().next();
+        let (left_flanking, right_flanking) = flanking(before, after);
+        let (can_open, can_close) = match ch {
+            '*' | '~' => (left_flanking, right_flanking),
+            _ => (
+                left_flanking && (!right_flanking || is_punctuation(before)),
+                right_flanking && (!left_flanking || is_punctuation(after)),
+            ),
+        };
+
+        self.position = end;
+        self.push(
+            Text(ast::Text::new(self.input[start..end].to_string())),
+            start,
+        );
+        self.delimiters.push(Delimiter {
+            ch,
+            index: self.nodes.len() - 1,
+            count: length,
+            length,
+            can_open,
+            can_close,
+        });
+    }
+
+    /// Resolve emphasis between the delimiters above the stack bottom.
+    ///
+    /// Delimiters are matched first, using the stack as a linked list so that
+    /// exhausted runs can be dropped cheaply, and the nodes above the bottom
+    /// are then rebuilt in a single pass.
+    fn process_emphasis(&mut self, bottom: usize) {
+        let len = self.delimiters.len();
+        if bottom >= len {
+            return;
+        }
+        let mut prev: Vec<Option<usize>> = (0..len).map(|i| i.checked_sub(1)).collect();
+        let mut next: Vec<Option<usize>> =
+            (1..=len).map(|i| Some(i).filter(|i| *i < len)).collect();
+        prev[bottom] = None;
+        // the delimiters used by each run to close and open emphasis, in the
+        // order they are matched
+        let mut closes: Vec<Vec<usize>> = vec![Vec::new(); len];
+        let mut opens: Vec<Vec<usize>> = vec![Vec::new(); len];
+        // the lowest opener worth trying for each kind of closer, since
+        // everything below it has already failed to match
+        let mut openers_bottom: HashMap<(char, bool, usize), usize> = HashMap::new();
+
+        let unlink = |prev: &mut Vec<Option<usize>>, next: &mut Vec<Option<usize>>, i: usize| {
+            if let Some(p) = prev[i] {
+                next[p] = next[i];
+            }
+            if let Some(n) = next[i] {
+                prev[n] = prev[i];
+            }
+        };
+
+        let mut closer = Some(bottom);
+        while let Some(c) = closer {
+            if !self.delimiters[c].can_close {
+                closer = next[c];
+                continue;
+            }
+
+            let d = &self.delimiters[c];
+            let key = (d.ch, d.can_open, d.length % 3);
+            let floor = openers_bottom.get(&key).copied().unwrap_or(bottom);
+            let mut candidate = prev[c].filter(|o| *o >= floor);
+            let opener = loop {
+                let Some(o) = candidate else { break None };
+                let (od, cd) = (&self.delimiters[o], &self.delimiters[c]);
+                let matches = match cd.ch {
+                    // strikethrough runs must be the same length
+                    '~' => od.count == cd.count,
+                    _ => !multiple_of_three(od, cd),
+                };
+                if od.ch == cd.ch && od.can_open && matches {
+                    break Some(o);
+                }
+                candidate = prev[o].filter(|o| *o >= floor);
+            };
+
+            let Some(o) = opener else {
+                openers_bottom.insert(key, c);
+                closer = next[c];
+                // a closer without an opener is only useful as an opener
+                if !self.delimiters[c].can_open {
+                    unlink(&mut prev, &mut next, c);
+                }
+                continue;
+            };
+
+            let (od, cd) = (&self.delimiters[o], &self.delimiters[c]);
+            let used = if cd.ch == '~' {
+                cd.count
+            } else if od.count >= 2 && cd.count >= 2 {
+                2
+            } else {
+                1
+            };
+            self.delimiters[o].count -= used;
+            self.delimiters[c].count -= used;
+            opens[o].push(used);
+            closes[c].push(used);
+
+            // delimiters between the two can no longer match
+            next[o] = Some(c);
+            prev[c] = Some(o);
+            if self.delimiters[o].count == 0 {
+                unlink(&mut prev, &mut next, o);
+            }
+            if self.delimiters[c].count == 0 {
+                closer = next[c];
+                unlink(&mut prev, &mut next, c);
+            }
+        }
+
+        // rebuild the nodes, wrapping the content between matched delimiters;
+        // a run closes from its start and opens from its end, innermost
+        // first
+        let first = self.delimiters[bottom].index;
+        let tail: Vec<Node> = self.nodes.drain(first..).collect();
+        let mut stack: Vec<(Point, Vec<Node>)> = vec![(Point::default(), Vec::new())];
+        let mut delimiter = bottom;
+        for (i, node) in tail.into_iter().enumerate() {
+            if delimiter >= len || self.delimiters[delimiter].index != first + i {
+                stack
+                    .last_mut()
+                    .expect("stack should not be empty")
+                    .1
+                    .push(node);
+                continue;
+            }
+            let Delimiter { ch, count, .. } = self.delimiters[delimiter];
+            let start = node.position().start;
+            let mut offset = 0;
+            for &used in &closes[delimiter] {
+                offset += used;
+                let (open, children) = stack.pop().expect("closer should have an opener");
+                let node = match (ch, used) {
+                    ('~', _) => Strikethrough(Strikethrough::new(children)),
+                    (_, 2) => Strong(Strong::new(children)),
+                    _ => Emphasis(Emphasis::new(children)),
+                };
+                let position = Position::new(open, start.add(offset));
+                let parent = &mut stack.last_mut().expect("stack should not be empty").1;
+                parent.push(node.with_position(position));
+            }
+            if count > 0 {
+                let text = ast::Text::new(ch.to_string().repeat(count));
+                let position = Position::new(start.add(offset), start.add(offset + count));
+                let parent = &mut stack.last_mut().expect("stack should not be empty").1;
+                parent.push(Text(text).with_position(position));
+                offset += count;
+            }
+            for &used in opens[delimiter].iter().rev() {
+                stack.push((start.add(offset), Vec::new()));
+                offset += used;
+            }
+            delimiter += 1;
+        }
+        let (_, nodes) = stack.pop().expect("stack should not be empty");
+        debug_assert!(stack.is_empty(), "every opener should be closed");
+        self.nodes.extend(nodes);
+        self.delimiters.truncate(bottom);
+    }
+}
+
+/// Scan the destination and optional title of an inline link, which follow
+/// the link text in parentheses. Returns the destination, the title, and the
+/// number of bytes consumed.
+fn scan_inline_link(input: &str) -> Option<(String, Option<String>, usize)> {
+    let mut position = 0;
+    if !input.starts_with('(') {
+        return None;
+    }
+    position += 1;
+    position += scan_whitespace(&input[position..]);
+    let (destination, len) = match scan_link_destination(&input[position..]) {
+        Some(destination) => destination,
+        None if input[position..].starts_with(')') => (String::new(), 0),
+        None => return None,
+    };
+    position += len;
+
+    let space = scan_whitespace(&input[position..]);
+    let mut title = None;
+    if space > 0 || len == 0 {
+        if let Some((text, len)) = scan_link_title(&input[position + space..]) {
+            title = Some(text);
+            position += space + len;
+        }
+    }
+    position += scan_whitespace(&input[position..]);
+
+    if !input[position..].starts_with(')') {
+        return None;
+    }
+    Some((destination, title, position + 1))
+}
+
+/// Scan a link reference definition. Returns the label, the definition, and
+/// the number of bytes consumed.
+pub fn scan_definition(input: &str) -> Option<(&str, Definition, usize)> {
+    let (label, mut position) = scan_link_label(input)?;
+    if label.trim().is_empty() || !input[position..].starts_with(':') {
+        return None;
+    }
+    position += 1;
+    position += scan_whitespace(&input[position..]);
+    let (destination, len) = scan_link_destination(&input[position..])?;
+    position += len;
+
+    // the title must be separated from the destination by whitespace and
+    // followed only by whitespace on its line
+    let space = scan_whitespace(&input[position..]);
+    if space > 0 {
+        if let Some((title, len)) = scan_link_title(&input[position + space..]) {
+            let end = position + space + len;
+            if let Some(len) = scan_line_end(&input[end..]) {
+                let title = Some(title);
+                return Some((label, Definition { destination, title }, end + len));
+            }
+        }
+    }
+    let len = scan_line_end(&input[position..])?;
+    let title = None;
+    Some((label, Definition { destination, title }, position + len))
+}
+
+/// Scan a link label, including its brackets. Returns the label and the
+/// number of bytes consumed.
+pub fn scan_link_label(input: &str) -> Option<(&str, usize)> {
+    if !input.starts_with('[') {
+        return None;
+    }
+    let mut chars = input.char_indices().skip(1);
+    let mut count = 0;
+    while let Some((i, ch)) = chars.next() {
+        count += 1;
+        if count > 999 {
+            return None;
+        }
+        match ch {
+            ']' => return Some((&input[1..i], i + 1)),
+            '[' => return None,
+            '\\' => {
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Normalize a link label for matching by folding case and collapsing
+/// whitespace.
+pub fn normalize_label(label: &str) -> String {
+    label
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .to_uppercase()
+}
+
+/// The maximum nesting of parentheses in a link destination.
+const MAX_LINK_PAREN_DEPTH: usize = 32;
+
+/// Scan a link destination. Returns the destination and the number of bytes
+/// consumed.
+pub fn scan_link_destination(input: &str) -> Option<(String, usize)> {
+    let mut chars = input.char_indices();
+
+    // pointy brackets
+    if input.starts_with('<') {
+        chars.next();
+        while let Some((i, ch)) = chars.next() {
+            match ch {
+                '>' => return Some((unescape(&input[1..i]), i + 1)),
+                '<' | '\n' => return None,
+                '\\' => {
+                    chars.next();
+                }
+                _ => {}
+            }
+        }
+        return None;
+    }
+
+    // balanced parentheses
+    let mut depth = 0;
+    let mut end = input.len();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' => {
+                chars.next();
+            }
+            // nesting is limited, as in cmark, so that scanning stays
+            // linear
+            '(' if depth == MAX_LINK_PAREN_DEPTH => return None,
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                end = i;
+                break;
+            }
+            ')' => depth -= 1,
+            _ if ch == ' ' || ch.is_ascii_control() => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || end == 0 {
+        return None;
+    }
+    Some((unescape(&input[..end]), end))
+}
+
+/// Scan a link title. Returns the title and the number of bytes consumed.
+pub fn scan_link_title(input: &str) -> Option<(String, usize)> {
+    let close = match input.chars().next() {
+        Some('"') => '"',
+        Some('\'') => '\'',
+        Some('(') => ')',
+        _ => return None,
+    };
+    let mut chars = input.char_indices().skip(1);
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            _ if ch == close => return Some((unescape(&input[1..i]), i + 1)),
+            '(' if close == ')' => return None,
+            '\\' => {
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Resolve the backslash escapes and character references in a text.
+pub fn unescape(input: &str) -> String {
+    let mut output = String::new();
+    let mut position = 0;
+    while let Some(offset) = input[position..].find(['\\', '&']) {
+        output.push_str(&input[position..position + offset]);
+        position += offset;
+        let rest = &input[position..];
+        if let Some((decoded, len)) = scan_character_reference(rest) {
+            output.push_str(&decoded);
+            position += len;
+        } else {
+            match rest[1..].chars().next() {
+                Some(ch) if rest.starts_with('\\') && ch.is_ascii_punctuation() => {
+                    output.push(ch);
+                    position += 2;
+                }
+                _ => {
+                    output.push_str(&rest[..1]);
+                    position += 1;
+                }
+            }
+        }
+    }
+    output.push_str(&input[position..]);
+    output
+}
+
+/// Scan an entity or numeric character reference. Returns the referenced
+/// characters and the number of bytes consumed.
+pub fn scan_character_reference(input: &str) -> Option<(String, usize)> {
+    let cap = CHARACTER_REFERENCE_RE.captures(input)?;
+    let len = cap.get(0).unwrap().len();
+    let code = match (cap.get(1), cap.get(2), cap.get(3)) {
+        (Some(name), _, _) => {
+            let characters = ENTITIES.get(name.as_str())?;
+            return Some((characters.to_string(), len));
+        }
+        (None, Some(decimal), _) => decimal.as_str().parse::<u32>().ok()?,
+        (None, None, Some(hex)) => u32::from_str_radix(hex.as_str(), 16).ok()?,
+        _ => unreachable!("one reference form should match"),
+    };
+    let ch = match char::from_u32(code) {
+        Some('\0') | None => char::REPLACEMENT_CHARACTER,
+        Some(ch) => ch,
+    };
+    Some((ch.to_string(), len))
+}
+
+/// Scan spaces and tabs, including up to one line ending. Returns the number
+/// of bytes consumed.
+pub fn scan_whitespace(input: &str) -> usize {
+    let mut newline = false;
+    for (i, ch) in input.char_indices() {
+        match ch {
+            ' ' | '\t' => {}
+            '\n' if !newline => newline = true,
+            _ => return i,
+        }
+    }
+    input.len()
+}
+
+/// Scan spaces and tabs up to the end of the line. Returns the number of bytes
+/// consumed, including the line ending.
+fn scan_line_end(input: &str) -> Option<usize> {
+    let len = input.len() - input.trim_start_matches([' ', '\t']).len();
+    match &input[len..] {
+        "" => Some(len),
+        rest if rest.starts_with('\n') => Some(len + 1),
+        _ => None,
+    }
+}
+
+/// Returns whether a delimiter run is left-flanking and right-flanking, given
+/// the characters on either side of it.
+fn flanking(before: Option<char>, after: Option<char>) -> (bool, bool) {
+    let left = !is_whitespace(after)
+        && (!is_punctuation(after) || is_whitespace(before) || is_punctuation(before));
+    let right = !is_whitespace(before)
+        && (!is_punctuation(before) || is_whitespace(after) || is_punctuation(after));
+    (left, right)
+}
+
+/// Returns whether a pair of delimiters is prevented from matching by the
+/// "multiple of 3" rule.
+fn multiple_of_three(opener: &Delimiter, closer: &Delimiter) -> bool {
+    (opener.can_close || closer.can_open)
+        && (opener.length + closer.length).is_multiple_of(3)
+        && !(opener.length.is_multiple_of(3) && closer.length.is_multiple_of(3))
+}
+
+/// Returns whether a character is Unicode whitespace. The start and end of
+/// the input count as whitespace.
+fn is_whitespace(ch: Option<char>) -> bool {
+    match ch {
+        Some(ch) => matches!(ch, '\t' | '\n' | '\u{c}' | '\r') || ch.is_whitespace(),
+        None => true,
+    }
+}
+
+/// Returns whether a character is Unicode punctuation.
+fn is_punctuation(ch: Option<char>) -> bool {
+    match ch {
+        Some(ch) => {
+            ch.is_ascii_punctuation() || PUNCTUATION_RE.is_match(ch.encode_utf8(&mut [0; 4]))
+        }
+        None => false,
+    }
+}
+
+/// Merge adjacent text nodes and drop empty ones.
+fn normalize(nodes: Vec<Node>) -> Vec<Node> {
+    let mut output: Vec<Node> = vec![];
+    for mut node in nodes {
+        if let Some(children) = node.children_mut() {
+            *children = normalize(std::mem::take(children));
+        }
+        match (output.last_mut(), node) {
+            (_, Text(text)) if text.text.is_empty() => {}
+            (Some(Text(last)), Text(text)) => {
+                last.text.push_str(&text.text);
+                last.position.end = text.position.end;
+            }
+            (_, node) => output.push(node),
+        }
+    }
+    output
+}
+
+/// Turns the extended autolinks in text nodes into autolink nodes, except
+/// within links and images.
+fn extended_autolinks(nodes: Vec<Node>) -> Vec<Node> {
+    let mut output = vec![];
+    for mut node in nodes {
+        match node {
+            Text(text) => output.extend(split_autolinks(text)),
+            Link(_) | Image(_) => output.push(node),
+            _ => {
+                if let Some(children) = node.children_mut() {
+                    *children = extended_autolinks(std::mem::take(children));
+                }
+                output.push(node);
+            }
+        }
+    }
+    output
+}
+
+/// Splits a text node at its extended autolinks.
+fn split_autolinks(text: ast::Text) -> Vec<Node> {
+    let input = &text.text;
+    let position = text.position;
+    // positions within the text are only known if it matches its source
+    let exact = position.start.line == position.end.line
+        && position.end.offset - position.start.offset == input.len();
+    let span = |start: usize, end: usize| match exact {
+        true => Position::new(position.start.add(start), position.start.add(end)),
+        false => position,
+    };
+    let mut nodes = vec![];
+    let mut last = 0;
+    let mut index = 0;
+    while let Some(ch) = input[index..].chars().next() {
+        let boundary = match input[..index].chars().next_back() {
+            None => true,
+            Some(prev) => prev.is_whitespace() || matches!(prev, '*' | '_' | '~' | '('),
+        };
+        let found = match ch {
+            '@' => scan_email_autolink(input, last, index),
+            _ if boundary => scan_url_autolink(&input[index..])
+                .map(|(len, destination)| (index, index + len, destination)),
+            _ => None,
+        };
+        let Some((start, end, destination)) = found else {
+            index += ch.len_utf8();
+            continue;
+        };
+        if last < start {
+            let text = ast::Text::new(input[last..start].to_string());
+            nodes.push(Text(text).with_position(span(last, start)));
+        }
+        let autolink = ast::Autolink::new(destination, input[start..end].to_string());
+        nodes.push(Autolink(autolink).with_position(span(start, end)));
+        last = end;
+        index = end;
+    }
+    if last == 0 {
+        return vec![Text(text)];
+    }
+    if last < input.len() {
+        let text = ast::Text::new(input[last..].to_string());
+        nodes.push(Text(text).with_position(span(last, input.len())));
+    }
+    nodes
+}
+
+/// Scans an extended www, url or protocol autolink at the start of the
+/// input. Returns its length and destination.
+fn scan_url_autolink(input: &str) -> Option<(usize, String)> {
+    for scheme in ["mailto:", "xmpp:"] {
+        let Some(rest) = input.strip_prefix(scheme) else {
+            continue;
+        };
+        let mut len = scheme.len() + scan_email(rest)?;
+        if scheme == "xmpp:" && input[len..].starts_with('/') {
+            let resource = input[len + 1..]
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '@' || c == '.'))
+                .unwrap_or(input.len() - len - 1);
+            let resource = input[len + 1..len + 1 + resource].trim_end_matches('.');
+            if !resource.is_empty() {
+                len += 1 + resource.len();
+            }
+        }
+        return Some((len, input[..len].to_string()));
+    }
+    let (scheme, prefix) = if input.starts_with("www.") {
+        ("", "http://")
+    } else {
+        let scheme = ["http://", "https://", "ftp://"]
+            .into_iter()
+            .find(|scheme| input.starts_with(scheme))?;
+        (scheme, "")
+    };
+    let domain = scheme.len() + scan_domain(&input[scheme.len()..])?;
+    let end = input[domain..]
+        .find(|c: char| c.is_whitespace() || c == '<')
+        .map_or(input.len(), |len| domain + len);
+    let len = trim_autolink(&input[..end]);
+    Some((len, format!("{prefix}{}", &input[..len])))
+}
+
+/// Scans a valid domain at the start of the input and returns its length.
+///
+/// A domain is made of segments of alphanumeric characters, underscores and
+/// hyphens, separated by at least one period, with no underscores in the last
+/// two segments.
+fn scan_domain(input: &str) -> Option<usize> {
+    let end = input
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.')))
+        .unwrap_or(input.len());
+    let domain = input[..end].trim_end_matches('.');
+    let segments: Vec<&str> = domain.split('.').collect();
+    let valid = segments.len() > 1
+        && segments.iter().all(|segment| !segment.is_empty())
+        && segments
+            .iter()
+            .rev()
+            .take(2)
+            .all(|segment| !segment.contains('_'));
+    valid.then_some(domain.len())
+}
+
+/// Trims the trailing punctuation, unmatched closing parentheses and
+/// entity-like suffixes from an autolink. Returns the trimmed length.
+fn trim_autolink(link: &str) -> usize {
+    let mut link = link;
+    loop {
+        link = match link.chars().next_back() {
+            Some('?' | '!' | '.' | ',' | ':' | '*' | '_' | '~') => &link[..link.len() - 1],
+            Some(')') if link.matches(')').count() > link.matches('(').count() => {
+                &link[..link.len() - 1]
+            }
+            Some(';') => {
+                let name =
+                    link[..link.len() - 1].trim_end_matches(|c: char| c.is_ascii_alphanumeric());
+                match name.strip_suffix('&') {
+                    Some(rest) if name.len() < link.len() - 1 => rest,
+                    _ => return link.len(),
+                }
+            }
+            _ => return link.len(),
+        }
+    }
+}
+
+/// Scans an email address at the start of the input and returns its length.
+fn scan_email(input: &str) -> Option<usize> {
+    let local = input
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-' | '_')))
+        .unwrap_or(input.len());
+    if local == 0 || !input[local..].starts_with('@') {
+        return None;
+    }
+    let rest = &input[local + 1..];
+    let end = rest
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_')))
+        .unwrap_or(rest.len());
+    let domain = rest[..end].trim_end_matches('.');
+    let valid = domain.contains('.')
+        && domain.split('.').all(|segment| !segment.is_empty())
+        && !domain.ends_with(['-', '_']);
+    valid.then_some(local + 1 + domain.len())
+}
+
+/// Scans an email autolink around the `@` at an index of the input, without
+/// going back past an earlier index. Returns its span and destination.
+fn scan_email_autolink(input: &str, from: usize, at: usize) -> Option<(usize, usize, String)> {
+    let start = input[from..at]
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '+' | '-' | '_')))
+        .map_or(from, |(index, c)| from + index + c.len_utf8());
+    let len = scan_email(&input[start..])?;
+    let text = &input[start..start + len];
+    Some((start, start + len, format!("mailto:{text}")))
+}
@@ -1,17 +1,77 @@
 //! This library can be used to parse Markdown text into HTML.
 
-mod ast;
+pub mod ast;
+pub mod event;
+pub mod html;
+mod inline;
+mod options;
+pub mod toc;
+pub mod visit;
 
-use std::cmp::max;
+use std::{cmp::max, collections::HashMap};
+
+#[allow(deprecated)]
+pub use ast::RenderOptions;
+use inline::{Definitions, SourceMap};
+pub use options::{Options, Slugger, SoftBreak};
 
 use ast::{
-    Heading, Html, HtmlType, ListProximity, ListType,
+    Alignment, Attributes, CodeBlockType, FrontMatterKind, Heading, Html, HtmlType, ListProximity,
+    ListType,
     Node::{self, *},
-    Paragraph, Root,
+    Paragraph, Point, Position, Root,
 };
 use lazy_static::lazy_static;
 use regex::Regex;
 
+/// Open or closing HTML tag.
+///
+/// Shared by HTML blocks and inline HTML; whitespace may include a line ending
+/// when matching inline content.
+pub(crate) const HTML_TAG: &str = r###"
+    (?:
+        # open
+        (?:
+            # start delim and tag name
+            <[[:alpha:]][[:alnum:]\-]*
+            # attributes
+            (?:
+                # leading space
+                \s+
+                # attribute name
+                [_:[:alpha:]][_.:\-[:alnum:]]*
+                # optional value spec
+                (?:
+                    \s*
+                    =
+                    \s*
+                    # value
+                    (?:
+                        '[^']*'
+                        |"[^"]*"
+                        |[^\s"'=<>`]+
+                    )
+                )?
+            )*
+            # trailing space
+            \s*
+            # optional close
+            /?
+            # end delim
+            >
+        )
+        # close
+        |(?:
+            # start delim and tag name
+            </[[:alpha:]][[:alnum:]\-]*
+            # trailing space
+            \s*
+            # end delim
+            >
+        )
+    )
+"###;
+
 lazy_static! {
     static ref HR_RE: Regex = Regex::new(
         r"(?x)
@@ -86,6 +146,15 @@ lazy_static! {
         "
     )
     .expect("fenced code regex should be valid");
+    static ref ATTRIBUTE_RE: Regex = Regex::new(
+        r#"(?x)
+        # name and value
+        ([A-Za-z_:][\w.:-]*)=(?:"([^"]*)"|([^\s"]*))
+        # id, class or something else
+        |(\S+)
+        "#
+    )
+    .expect("attribute regex should be valid");
     static ref BLOCKQUOTE_RE: Regex = Regex::new(
         r"(?x)
         # start of text
@@ -99,6 +168,19 @@ lazy_static! {
         "
     )
     .expect("blockquote regex should be valid");
+    static ref FOOTNOTE_DEFINITION_RE: Regex = Regex::new(
+        r"(?x)
+        # start of text
+        ^
+        # leading spaces
+        \ {0,3}
+        # label
+        \[\^([^\]\s]+)\]:
+        # trailing spaces
+        [\ \t]*
+        "
+    )
+    .expect("footnote definition regex should be valid");
     static ref LIST_ITEM_RE: Regex = Regex::new(
         r"(?x)
         # start of text
@@ -147,61 +229,8 @@ lazy_static! {
     // line begins with a complete open tag (with any tag name other than pre,
     // script, style, or textarea) or a complete closing tag, followed by zero or
     // more spaces and tabs, followed by the end of the line.
-    static ref HTML_7_RE: Regex = Regex::new(
-        r###"(?xi)
-        # start
-        ^
-        # tag
-        (?:
-            # open
-            (?:
-                # start delim and tag name
-                <[[:alpha:]][[:alnum:]\-]*
-                # attributes
-                (?:
-                    # leading space
-                    \ *
-                    # tag name
-                    [_:[:alpha:]][_.:\-[:alnum:]]*
-                    # optional value spec
-                    (?:
-                        \ *
-                        =
-                        \ *
-                        # value
-                        (?:
-                            '[^']*'
-                            |"[^"]*"
-                            |[^\ "'=<>`]+
-                        )
-                    )?
-                )*
-                # trailing space
-                \ *
-                # optional close
-                /?
-                # end delim
-                >
-            )
-            # close
-            |(?:
-                # start delim and tag name
-                </[[:alpha:]][[:alnum:]\-]*
-                # trailing space
-                \ *
-                # end delim
-                >
-            )
-        )
-        # end
-        $
-        "###
-    ).expect("html 7 regex should be valid");
-}
-
-enum CodeBlockType {
-    Fenced,
-    _Indented,
+    static ref HTML_7_RE: Regex =
+        Regex::new(&format!(r"(?xi)^{HTML_TAG}\s*$")).expect("html 7 regex should be valid");
 }
 
 /// Parse tree.
@@ -267,6 +296,16 @@ impl Tree {
         self.stack.push(index);
     }
 
+    /// Extend the current node and its ancestors up to an end point.
+    fn extend(&mut self, end: Point) {
+        let mut node = &mut self.root;
+        node.position_mut().end = end;
+        for i in &self.stack {
+            node = &mut node.children_mut().expect("should be parent")[*i];
+            node.position_mut().end = end;
+        }
+    }
+
     /// Remove the current node from the tree.
     fn remove(&mut self) {
         self.parent_mut()
@@ -288,29 +327,300 @@ impl Tree {
 /// assert_eq!(html, "<p>hello world</p>\n")
 /// ```
 pub fn to_html(text: &str) -> String {
-    use CodeBlockType::*;
+    to_html_with_options(text, &Options::default())
+}
 
-    let mut tree = Tree::new();
+/// Parses an input Markdown text into HTML with the given options.
+///
+/// # Examples
+///
+/// ```
+/// use markdown::{Options, SoftBreak};
+///
+/// let options = Options::new().soft_break(SoftBreak::Space);
+/// let html = markdown::to_html_with_options("hello\nworld", &options);
+/// assert_eq!(html, "<p>hello world</p>\n")
+/// ```
+pub fn to_html_with_options(text: &str, options: &Options) -> String {
+    let mut html = String::new();
+    parse_with_options(text, options)
+        .render(&mut html, options)
+        .expect("rendering to a string should not fail");
+    html
+}
+
+/// Parses an input Markdown text into HTML, rendering soft line breaks in the
+/// given style.
+#[deprecated(note = "use `to_html_with_options` instead")]
+pub fn to_html_with_soft_break(text: &str, soft_break: SoftBreak) -> String {
+    to_html_with_options(text, &Options::new().soft_break(soft_break))
+}
+
+/// Parses an input Markdown text into HTML with the given rendering options.
+#[deprecated(note = "use `to_html_with_options` instead")]
+#[allow(deprecated)]
+pub fn to_html_with_render_options(text: &str, options: RenderOptions) -> String {
+    to_html_with_options(text, &options.into())
+}
+
+/// Parses an input Markdown text into a syntax tree.
+///
+/// The returned node is always a [`Node::Root`]. It can be inspected or
+/// transformed, and then rendered into HTML with its [`Display`] implementation
+/// or [`Node::render`]. Every node records its [`Position`] in the input.
+///
+/// # Examples
+///
+/// ```
+/// use markdown::ast::Node;
+///
+/// let node = markdown::parse("# hello world");
+/// let Node::Root(root) = &node else { panic!() };
+/// assert!(matches!(root.children[0], Node::Heading(_)));
+/// assert_eq!(node.to_string(), "<h1>hello world</h1>\n");
+/// ```
+///
+/// [`Display`]: std::fmt::Display
+pub fn parse(text: &str) -> Node {
+    parse_with_options(text, &Options::default())
+}
 
-    let mut chunk_separators = vec![];
-    let mut code_block_type: Option<CodeBlockType> = None;
-    let mut fenced_block_delim: Option<String> = None;
-    let mut fenced_block_lead: Option<u8> = None;
+/// Parses an input Markdown text into a syntax tree with the given options.
+pub fn parse_with_options(text: &str, options: &Options) -> Node {
+    let mut parser = Parser::with_options(options.clone());
+    parser.feed(text);
+    parser.finish()
+}
+
+/// Incremental Markdown parser.
+///
+/// Text can be fed in chunks of any size, such as reads from a pipe or the
+/// tokens of a stream. Lines that are split across chunks are joined before
+/// they are parsed, so the final tree is the same as [`parse`] returns for the
+/// whole text.
+///
+/// Top-level blocks can be taken with [`Parser::closed_blocks`] as soon as
+/// they are closed. Their inline content is parsed with the link reference
+/// definitions seen so far, so references to definitions that come later in
+/// the text are left as plain text.
+///
+/// # Examples
+///
+/// ```
+/// let mut parser = markdown::Parser::new();
+/// parser.feed("# hel");
+/// parser.feed("lo\n\nwor");
+/// let blocks = parser.closed_blocks();
+/// assert_eq!(blocks[0].to_string(), "<h1>hello</h1>\n");
+/// parser.feed("ld");
+/// assert_eq!(parser.finish().to_string(), "<p>world</p>\n");
+/// ```
+pub struct Parser {
+    /// Parsing options.
+    options: Options,
+    /// Blocks parsed so far.
+    tree: Tree,
+    /// Text after the last line ending, waiting for the rest of its line.
+    buffer: String,
+    /// Link reference and footnote definitions of the blocks that have been
+    /// taken.
+    definitions: Definitions,
+    /// The lines and line lengths of front matter that has not been closed
+    /// yet, which are parsed as Markdown if it never is.
+    front_matter: Option<Vec<(String, usize)>>,
+    /// The number of times each heading ID has been repeated so far, used to
+    /// make IDs unique.
+    heading_ids: HashMap<String, usize>,
+    /// Blank lines inside an indented code block, which are only part of
+    /// the block if it continues after them.
+    chunk_separators: Vec<String>,
+    last_line_blank: bool,
+    /// Blocks that are still open after a non-blank line extend to its end,
+    /// which is applied before the next line is processed.
+    open_end: Option<Point>,
+    /// The end of the last line.
+    end: Point,
+    /// The number of lines parsed so far.
+    number: usize,
+    /// The byte offset of the next line.
+    offset: usize,
+}
 
-    let mut last_line_blank = false;
+impl Default for Parser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser {
+    /// Create a new parser with the default options.
+    pub fn new() -> Self {
+        Self::with_options(Options::default())
+    }
+
+    /// Create a new parser with the given options.
+    pub fn with_options(options: Options) -> Self {
+        Self {
+            options,
+            tree: Tree::new(),
+            buffer: String::new(),
+            definitions: Definitions::new(),
+            front_matter: None,
+            heading_ids: HashMap::new(),
+            chunk_separators: vec![],
+            last_line_blank: false,
+            open_end: None,
+            end: Point::default(),
+            number: 0,
+            offset: 0,
+        }
+    }
+
+    /// Parse a chunk of text. A trailing partial line is kept until the rest
+    /// of it is fed, or until the parser is finished.
+    pub fn feed(&mut self, text: &str) {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.push_str(text);
+        let mut start = 0;
+        while let Some(i) = buffer[start..].find('\n') {
+            let line = &buffer[start..start + i];
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            self.parse_line(line, i + 1);
+            start += i + 1;
+        }
+        buffer.drain(..start);
+        self.buffer = buffer;
+    }
+
+    /// Take the top-level blocks that have been closed since the last call.
+    ///
+    /// The blocks are fully parsed and are removed from the tree that
+    /// [`Parser::finish`] returns.
+    pub fn closed_blocks(&mut self) -> Vec<Node> {
+        let children = self.tree.root.children_mut().expect("root is parent");
+        let count = match self.tree.stack.first_mut() {
+            Some(i) => std::mem::take(i),
+            None => children.len(),
+        };
+        let blocks = children.drain(..count).collect();
+        let mut root = Root(ast::Root {
+            children: blocks,
+            position: Position::default(),
+        });
+        self.resolve(&mut root);
+        match root {
+            Root(root) => root.children,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Parse the rest of the text and return the syntax tree, which is always
+    /// a [`Node::Root`].
+    pub fn finish(mut self) -> Node {
+        let buffer = std::mem::take(&mut self.buffer);
+        if !buffer.is_empty() {
+            self.parse_line(&buffer, buffer.len());
+        }
+        if let Some(lines) = self.front_matter.take() {
+            // the lines were not front matter after all
+            self.options.front_matter = false;
+            for (line, len) in lines {
+                self.parse_line(&line, len);
+            }
+        }
+        if let Some(end) = self.open_end {
+            self.tree.extend(end);
+        }
+        self.tree.root.position_mut().end = self.end;
+
+        let mut root = std::mem::replace(&mut self.tree.root, Root(ast::Root::new()));
+        self.resolve(&mut root);
+        root
+    }
+
+    /// Finish the closed blocks under a node: collect link reference and
+    /// footnote definitions, then parse inline content, give headings IDs and
+    /// tighten lists.
+    fn resolve(&mut self, node: &mut Node) {
+        collect_definitions(node, &mut self.definitions);
+        if self.options.tasklist {
+            resolve_tasks(node);
+        }
+        resolve_inlines(node, &self.definitions, &self.options);
+        if self.options.heading_ids {
+            resolve_heading_ids(node, &self.options.slugger, &mut self.heading_ids);
+        }
+        tighten(node);
+    }
+
+    /// Hold back the lines of front matter at the start of the input until
+    /// its closing delimiter, then add it to the tree. Returns whether the
+    /// line was taken.
+    fn scan_front_matter(&mut self, line: &str, len: usize) -> bool {
+        let lines = match &mut self.front_matter {
+            Some(lines) => lines,
+            None if self.number == 0 && front_matter_kind(line).is_some() => {
+                self.front_matter = Some(vec![(line.to_string(), len)]);
+                return true;
+            }
+            None => return false,
+        };
+        lines.push((line.to_string(), len));
+        let kind = front_matter_kind(&lines[0].0).expect("front matter should be delimited");
+        let closed = match kind {
+            FrontMatterKind::Yaml => matches!(line.trim_end(), "---" | "..."),
+            FrontMatterKind::Toml => line.trim_end() == "+++",
+        };
+        if !closed {
+            return true;
+        }
+
+        let lines = self.front_matter.take().unwrap_or_default();
+        let mut text = String::new();
+        let mut start = None;
+        for (i, (line, len)) in lines.iter().enumerate() {
+            self.number += 1;
+            let src = SourceLine::new(line, self.number, self.offset);
+            self.offset += len;
+            self.end = src.end();
+            start.get_or_insert(src.point(line));
+            if i > 0 && i < lines.len() - 1 {
+                text.push_str(&format!("{line}\n"));
+            }
+        }
+        let start = start.unwrap_or_default();
+        let front_matter = FrontMatter(ast::FrontMatter::new(kind, text));
+        self.tree
+            .push(front_matter.with_position(Position::new(start, self.end)));
+        self.tree.pop();
+        true
+    }
+
+    /// Parse a single line, given the length of the line and its ending.
+    fn parse_line(&mut self, line: &str, len: usize) {
+        use CodeBlockType::*;
+
+        if self.options.front_matter && self.scan_front_matter(line, len) {
+            return;
+        }
+
+        self.number += 1;
+        let src = SourceLine::new(line, self.number, self.offset);
+        self.offset += len;
+        if let Some(end) = self.open_end.take() {
+            self.tree.extend(end);
+        }
+        if !line.trim().is_empty() {
+            self.open_end = Some(src.end());
+        }
+        self.end = src.end();
 
-    for line in text.lines() {
         // Close unmatched containers
-        let len = tree.stack.len();
+        let len = self.tree.stack.len();
         let (matched, line, mut _remaining_space) =
-            matched_containers(&mut tree, line, last_line_blank);
-        for _ in matched..tree.stack.len() {
-            if let (Code(_), Some(Fenced)) = (tree.cur_mut(), &code_block_type) {
-                code_block_type = None;
-                fenced_block_delim = None;
-                fenced_block_lead = None;
-            }
-            tree.pop();
+            matched_containers(&mut self.tree, line, self.last_line_blank);
+        for _ in matched..self.tree.stack.len() {
+            self.tree.pop();
         }
 
         let dropped = len - matched;
@@ -320,32 +630,68 @@ pub fn to_html(text: &str) -> String {
         let mut line = line;
         loop {
             // ignore in fenced code block
-            if let (Code(_), Some(Fenced)) = (tree.cur_mut(), &code_block_type) {
+            if let Code(ast::Code {
+                block_type: Fenced(..),
+                ..
+            }) = self.tree.cur_mut()
+            {
                 break;
             }
 
             // on a loop, check if we match a block quote
             // if we do, advance line, loop again, otherwise break
             if let Some(cap) = BLOCKQUOTE_RE.captures(line) {
-                if let Paragraph(_) = tree.cur_mut() {
-                    tree.pop();
+                if let Paragraph(_) | Table(_) = self.tree.cur_mut() {
+                    self.tree.pop();
                 }
-                if let (Code(_), None) = (tree.cur_mut(), &code_block_type) {
-                    tree.pop();
+                if let Code(ast::Code {
+                    block_type: Indented,
+                    ..
+                }) = self.tree.cur_mut()
+                {
+                    self.tree.pop();
                 }
-                tree.push(BlockQuote(ast::BlockQuote::new()));
+                let quote = BlockQuote(ast::BlockQuote::new());
+                self.tree
+                    .push(quote.with_position(src.span(line.trim_start())));
                 let delim = cap.get(0).unwrap().as_str();
                 _remaining_space = if delim.ends_with("\t") { 2 } else { 0 };
                 line = &line[delim.len()..];
+                could_be_lazy = false;
                 continue;
             };
 
+            if let (true, Some(cap)) = (
+                self.options.footnotes,
+                FOOTNOTE_DEFINITION_RE.captures(line),
+            ) {
+                if let Paragraph(_) | Table(_) | List(_) = self.tree.cur_mut() {
+                    self.tree.pop();
+                }
+                if let Code(ast::Code {
+                    block_type: Indented,
+                    ..
+                }) = self.tree.cur_mut()
+                {
+                    self.tree.pop();
+                }
+                // the content of a definition is indented by four spaces
+                let definition = ast::FootnoteDefinition::new(cap[1].to_string(), 4);
+                self.tree.push(
+                    FootnoteDefinition(definition).with_position(src.span(line.trim_start())),
+                );
+                _remaining_space = 0;
+                line = &line[cap[0].len()..];
+                could_be_lazy = false;
+                continue;
+            }
+
             if let Some(cap) = LIST_ITEM_RE.captures(line) {
                 // if it could be a thematic break, that interpretation takes
                 // precedence
                 if HR_RE.is_match(line) {
-                    if let List(_) = tree.cur_mut() {
-                        tree.pop();
+                    if let List(_) = self.tree.cur_mut() {
+                        self.tree.pop();
                     }
                     break;
                 }
@@ -353,20 +699,25 @@ pub fn to_html(text: &str) -> String {
                 let delim = cap.get(1).expect("delim should exist").as_str();
                 let trail_len = cap.get(2).unwrap().len();
 
-                if let Paragraph(_) = tree.cur_mut() {
+                if let Table(_) = self.tree.cur_mut() {
+                    self.tree.pop();
+                }
+
+                if let Paragraph(_) = self.tree.cur_mut() {
                     // empty list cannot interrupt paragraph
                     if trail_len == 0 {
                         break;
-                    } else if !delim.ends_with(")") && !delim.ends_with(".") {
-                        tree.pop();
-                    } else if delim == "1)" || delim == "1." {
-                        tree.pop();
+                    } else if (!delim.ends_with(')') && !delim.ends_with('.'))
+                        || delim == "1)"
+                        || delim == "1."
+                    {
+                        self.tree.pop();
                     } else {
                         break;
                     }
                 }
 
-                if !matches!(tree.cur_mut(), List(_)) {
+                if !matches!(self.tree.cur_mut(), List(_)) {
                     let list_type = match delim.chars().last().expect("last char should exist") {
                         c @ ')' | c @ '.' => {
                             ast::ListType::Ordered(c, delim[..delim.len() - 1].parse().unwrap())
@@ -374,12 +725,14 @@ pub fn to_html(text: &str) -> String {
                         c @ '-' | c @ '+' | c @ '*' => ast::ListType::Unordered(c),
                         _ => unreachable!(),
                     };
-                    tree.push(List(ast::List::new(list_type)));
+                    let list = List(ast::List::new(list_type));
+                    self.tree
+                        .push(list.with_position(src.span(line.trim_start())));
                 }
 
                 let delim_ = cap.get(0).unwrap().as_str();
                 let mut indent = delim_.len();
-                if cap.get(2).unwrap().len() == 0 {
+                if cap.get(2).unwrap().is_empty() {
                     indent = delim_.len();
                 } else if scan_indented_code(&line.trim_start()[delim.len() + 1..]) {
                     indent = delim_.trim_end().len() + 1;
@@ -392,7 +745,9 @@ pub fn to_html(text: &str) -> String {
                 } else {
                     _remaining_space = 0;
                 }
-                tree.push(ListItem(ast::ListItem::new(max(indent, 2))));
+                let item = ListItem(ast::ListItem::new(max(indent, 2)));
+                self.tree
+                    .push(item.with_position(src.span(line.trim_start())));
                 line = &line[indent..];
                 could_be_lazy = false;
                 continue;
@@ -403,42 +758,61 @@ pub fn to_html(text: &str) -> String {
 
         // Blank line
         if line.trim().is_empty() {
-            match tree.cur_mut() {
-                Paragraph(_) => tree.pop(),
-                Code(code) => match code_block_type {
-                    Some(Fenced) => code.text.push_str(&format!("{line}\n")),
-                    _ => {
+            match self.tree.cur_mut() {
+                Paragraph(_) | Table(_) => self.tree.pop(),
+                Code(code) => match code.block_type {
+                    Fenced(..) => code.text.push_str(&format!("{line}\n")),
+                    Indented => {
                         let content = line.chars().skip(4).collect::<String>();
-                        chunk_separators.push(format!("{content}\n"));
+                        self.chunk_separators.push(format!("{content}\n"));
                     }
                 },
                 Html(html) => match html.html_type {
-                    HtmlType::Simple => tree.pop(),
+                    HtmlType::Simple | HtmlType::Custom => self.tree.pop(),
                     _ => html.text.push_str(&format!("{line}\n")),
                 },
                 _ => {}
             }
-            if !matches!(tree.cur_mut(), Node::BlockQuote(_) | Node::Code(_)) {
-                last_line_blank = true;
+            if !matches!(self.tree.cur_mut(), Node::BlockQuote(_) | Node::Code(_)) {
+                self.last_line_blank = true;
             }
-            if let ListItem(list_item) = tree.cur_mut() {
+            if let ListItem(list_item) = self.tree.cur_mut() {
                 if list_item.children.is_empty() {
-                    last_line_blank = false;
+                    self.last_line_blank = false;
                 }
             }
-            continue;
+            return;
         }
 
-        last_line_blank = false;
+        self.last_line_blank = false;
+
+        // Table row
+        if let Table(table) = self.tree.cur_mut() {
+            if !interrupts_table(line) {
+                let content = line.trim_start();
+                let mut cells = table_cells(content, src.point(content));
+                let columns = table.alignments.len();
+                cells.truncate(columns);
+                while cells.len() < columns {
+                    let end = Position::new(src.end(), src.end());
+                    cells.push(TableCell(ast::TableCell::new(vec![])).with_position(end));
+                }
+                let row = TableRow(ast::TableRow::new(cells));
+                table.children.push(row.with_position(src.span(content)));
+                return;
+            }
+            self.tree.pop();
+        }
 
         // HTML
-        if let Html(html) = tree.cur_mut() {
+        if let Html(html) = self.tree.cur_mut() {
             let content = format!("{line}\n");
             html.text.push_str(&content);
             if end_condition_met(html, line) {
-                tree.pop();
+                html.position.end = src.end();
+                self.tree.pop();
             }
-            continue;
+            return;
         }
 
         if let Some(cap) = HTML_1_5_RE.captures(line) {
@@ -457,154 +831,276 @@ pub fn to_html(text: &str) -> String {
                 }
             };
 
-            if matches!(tree.cur_mut(), Paragraph(_)) {
-                tree.pop();
+            if matches!(self.tree.cur_mut(), Paragraph(_)) {
+                self.tree.pop();
             }
 
-            if matches!((tree.cur_mut(), &code_block_type), (Code(_), None)) {
-                tree.pop();
+            if matches!(
+                self.tree.cur_mut(),
+                Code(ast::Code {
+                    block_type: Indented,
+                    ..
+                })
+            ) {
+                self.tree.pop();
             }
 
-            tree.push(Html(ast::Html::new(content, html_type)));
-            if let Html(html) = tree.cur_mut() {
+            let html = Html(ast::Html::new(content, html_type));
+            self.tree
+                .push(html.with_position(src.span(line.trim_start())));
+            if let Html(html) = self.tree.cur_mut() {
                 if end_condition_met(html, line) {
-                    tree.pop();
+                    self.tree.pop();
                 }
             }
-            continue;
+            return;
         }
 
         if HTML_6_RE.is_match(line) {
             let content = format!("{line}\n");
 
-            if matches!(tree.cur_mut(), Paragraph(_)) {
-                tree.pop();
+            if matches!(self.tree.cur_mut(), Paragraph(_)) {
+                self.tree.pop();
             }
 
-            if matches!((tree.cur_mut(), &code_block_type), (Code(_), None)) {
-                tree.pop();
+            if matches!(
+                self.tree.cur_mut(),
+                Code(ast::Code {
+                    block_type: Indented,
+                    ..
+                })
+            ) {
+                self.tree.pop();
             }
 
-            tree.push(Html(ast::Html::new(content, HtmlType::Simple)));
-            if let Html(html) = tree.cur_mut() {
+            let html = Html(ast::Html::new(content, HtmlType::Simple));
+            self.tree
+                .push(html.with_position(src.span(line.trim_start())));
+            if let Html(html) = self.tree.cur_mut() {
                 if end_condition_met(html, line) {
-                    tree.pop();
+                    self.tree.pop();
                 }
             }
-            continue;
+            return;
         }
 
         if HTML_7_RE.is_match(line) {
             // paragraph takes precedence over html 7 block
-            if !matches!(tree.cur_mut(), Paragraph(_)) {
+            if !matches!(self.tree.cur_mut(), Paragraph(_)) {
                 let content = format!("{line}\n");
-                tree.push(Html(ast::Html::new(content, HtmlType::Custom)));
-                continue;
+                let html = Html(ast::Html::new(content, HtmlType::Custom));
+                self.tree
+                    .push(html.with_position(src.span(line.trim_start())));
+                return;
             }
         }
 
         // ATX heading
         if let Some(cap) = ATX_HEADING_RE.captures(line) {
-            if let Paragraph(_) = tree.cur_mut() {
-                tree.pop();
+            if let Paragraph(_) = self.tree.cur_mut() {
+                self.tree.pop();
             }
             let opening = cap.get(1).expect("opening sequence should be captured");
+            let mut attributes = Attributes::new();
             let content = match (cap.get(2), cap.get(3)) {
-                (Some(mat), None) => mat.as_str().to_string(),
-                (None, Some(mat)) => mat.as_str().to_string(),
-                (None, None) => String::new(),
+                (Some(mat), None) => mat.as_str().trim(),
+                // the attribute block comes after the closing sequence
+                (None, Some(mat)) => match split_attributes(mat.as_str(), &self.options) {
+                    Some((content, attrs)) => {
+                        attributes = attrs;
+                        strip_closing_sequence(content)
+                    }
+                    None => mat.as_str().trim(),
+                },
+                (None, None) => &line[line.len()..],
                 _ => unreachable!("cannot match on both"),
             };
-            tree.push(Heading(Heading::new(
-                opening.len() as u8,
-                vec![Text(content.trim().into())],
-            )));
-            tree.pop();
-            continue;
+            let text = Text(ast::Text::new(content.into())).with_position(src.span(content));
+            let mut heading = Heading::new(opening.len() as u8, vec![text]);
+            heading.id = attributes.id.take();
+            heading.attributes = attributes;
+            let heading = Heading(heading);
+            self.tree
+                .push(heading.with_position(src.span(line.trim_start())));
+            self.tree.pop();
+            return;
         }
 
         // Setext heading
-        if let (Some(cap), Paragraph(para)) = (SETEXT_HEADING_RE.captures(line), tree.cur_mut()) {
-            let children = para.children.clone();
+        if let (Some(cap), Paragraph(para)) =
+            (SETEXT_HEADING_RE.captures(line), self.tree.cur_mut())
+        {
+            // link reference definitions cannot be part of the heading
+            let text = raw_text(&para.children);
+            let len = scan_definitions(&text, &mut Definitions::new());
+            if len == text.len() {
+                para.children.push(raw_line(&src, line, true));
+                return;
+            }
             let level = if cap.get(1).is_some() { 1 } else { 2 };
-            tree.remove();
-            tree.push(Heading(Heading::new(level, children)));
-            tree.pop();
-            continue;
+            let (definitions, mut children) = split_raw(std::mem::take(&mut para.children), len);
+            // the attribute block ends the last line of the content
+            let mut attributes = Attributes::new();
+            if let Some(Text(last)) = children.last_mut() {
+                if let Some((content, attrs)) = split_attributes(&last.text, &self.options) {
+                    let removed = last.text.len() - content.len();
+                    last.text.truncate(content.len());
+                    let end = last.position.end;
+                    last.position.end =
+                        Point::new(end.line, end.column - removed, end.offset - removed);
+                    attributes = attrs;
+                }
+            }
+            let start = children[0].position().start;
+            if let Some(last) = definitions.last() {
+                para.position.end = last.position().end;
+                para.children = definitions;
+                self.tree.pop();
+            } else {
+                self.tree.remove();
+            }
+            let mut heading = Heading::new(level, children);
+            heading.id = attributes.id.take();
+            heading.attributes = attributes;
+            let heading = Heading(heading);
+            self.tree
+                .push(heading.with_position(Position::new(start, src.end())));
+            self.tree.pop();
+            return;
         }
 
         // Thematic break
         if HR_RE.is_match(line) {
-            if let Paragraph(_) = tree.cur_mut() {
-                tree.pop();
+            if let Paragraph(_) = self.tree.cur_mut() {
+                self.tree.pop();
             }
-            if let (Code(_), None) = (tree.cur_mut(), &code_block_type) {
-                tree.pop();
+            if let Code(ast::Code {
+                block_type: Indented,
+                ..
+            }) = self.tree.cur_mut()
+            {
+                self.tree.pop();
             }
-            tree.push(ThematicBreak);
-            tree.pop();
-            continue;
+            let hr = ThematicBreak(ast::ThematicBreak::new());
+            self.tree
+                .push(hr.with_position(src.span(line.trim_start())));
+            self.tree.pop();
+            return;
         }
 
         // Fenced code
-        match (
-            FENCED_CODE_RE.captures(line),
-            tree.cur_mut(),
-            &code_block_type,
-            &fenced_block_delim,
-            &fenced_block_lead,
-        ) {
-            (Some(cap), _, None, None, None) => {
-                if let Paragraph(_) = tree.cur_mut() {
-                    tree.pop();
-                }
-                let mut code = ast::Code::new();
-                if let Some(info) = cap.get(3) {
-                    if info.as_str().len() > 0 {
-                        code.info = Some(info.as_str().into());
-                    }
-                }
-                tree.push(Code(code));
-                code_block_type = Some(Fenced);
-                let delim = cap.get(2).unwrap().as_str();
-                fenced_block_delim = Some(delim.into());
-                let lead = cap.get(1).unwrap().len() as u8;
-                fenced_block_lead = Some(lead);
-                continue;
-            }
-            (None, Code(code), Some(Fenced), _, Some(lead)) => {
+        // the info string of a backtick fence cannot contain backticks
+        let fence = FENCED_CODE_RE
+            .captures(line)
+            .filter(|cap| !(cap[2].starts_with('`') && cap[3].contains('`')));
+        match (fence, self.tree.cur_mut()) {
+            (
+                None,
+                Code(
+                    code @ ast::Code {
+                        block_type: Fenced(..),
+                        ..
+                    },
+                ),
+            ) => {
                 let mut content = format!("{line}\n");
-                for _ in 0..*lead {
+                for _ in 0..code.indent {
                     if content.starts_with(' ') {
                         content.remove(0);
                     }
                 }
                 code.text.push_str(&content);
-                continue;
+                return;
             }
-            (Some(cap), Code(code), Some(Fenced), Some(op_delim), _) => {
+            (
+                Some(cap),
+                Code(
+                    code @ ast::Code {
+                        block_type: Fenced(..),
+                        ..
+                    },
+                ),
+            ) => {
+                let Fenced(op_char, op_len) = code.block_type else {
+                    unreachable!("code block should be fenced");
+                };
                 let cl_delim = cap.get(2).unwrap().as_str();
-                let same_type =
-                    cl_delim.chars().nth(0).unwrap() == op_delim.as_str().chars().nth(0).unwrap();
-                let long_enough = cl_delim.len() >= op_delim.len();
+                let same_type = cl_delim.starts_with(op_char);
+                let long_enough = cl_delim.len() >= op_len;
                 let has_info = !cap.get(3).unwrap().is_empty();
                 if same_type && long_enough && !has_info {
-                    tree.pop();
-                    code_block_type = None;
-                    fenced_block_delim = None;
-                    fenced_block_lead = None;
+                    code.position.end = src.end();
+                    self.tree.pop();
                 } else {
                     code.text.push_str(&format!("{line}\n"));
                 }
-                continue;
+                return;
+            }
+            (Some(cap), _) => {
+                if let Paragraph(_)
+                | Code(ast::Code {
+                    block_type: Indented,
+                    ..
+                }) = self.tree.cur_mut()
+                {
+                    self.tree.pop();
+                }
+                let delim = cap.get(2).unwrap().as_str();
+                let fence_char = delim.chars().next().expect("fence should not be empty");
+                let mut code = ast::Code::new();
+                code.block_type = Fenced(fence_char, delim.len());
+                code.indent = cap.get(1).unwrap().len();
+                code.position = src.span(line.trim_start());
+                if let Some(info) = cap.get(3) {
+                    let mut info = info.as_str();
+                    if let Some((rest, attributes)) = split_attributes(info, &self.options) {
+                        info = rest.trim();
+                        code.attributes = attributes;
+                    }
+                    if !info.is_empty() {
+                        code.info = Some(inline::unescape(info));
+                    }
+                }
+                self.tree.push(Code(code));
+                return;
             }
             _ => {}
         }
 
+        // Table
+        // the last line of a paragraph is the header row of a table if it has
+        // as many cells as the delimiter row
+        if let (true, Some(alignments), Paragraph(para)) = (
+            self.options.tables,
+            table_alignments(line),
+            self.tree.cur_mut(),
+        ) {
+            if let Some(Text(header)) = para.children.last() {
+                let content = header.text.trim_start_matches('\n');
+                let cells = table_cells(content, header.position.start);
+                if cells.len() == alignments.len() {
+                    let start = header.position.start;
+                    let row = TableRow(ast::TableRow::new(cells)).with_position(header.position);
+                    para.children.pop();
+                    if let Some(last) = para.children.last() {
+                        para.position.end = last.position().end;
+                        self.tree.pop();
+                    } else {
+                        self.tree.remove();
+                    }
+                    let mut table = ast::Table::new(alignments);
+                    table.children.push(row);
+                    self.tree
+                        .push(Table(table).with_position(Position::new(start, src.end())));
+                    return;
+                }
+            }
+        }
+
         // Paragraph
-        if let Paragraph(para) = tree.cur_mut() {
-            para.children.push(Text(format!("\n{}", line.trim_start())));
-            continue;
+        if let Paragraph(para) = self.tree.cur_mut() {
+            para.children.push(raw_line(&src, line, true));
+            return;
         }
 
         // nothing else matched, which means this is a paragraph
@@ -612,61 +1108,64 @@ pub fn to_html(text: &str) -> String {
         // and treat it as a continuation line
         if dropped > 0 && could_be_lazy {
             for _ in 0..dropped {
-                tree.advance();
+                self.tree.advance();
             }
-            if let Paragraph(para) = tree.cur_mut() {
-                para.children.push(Text(format!("\n{}", line.trim_start())));
-                continue;
+            if let Paragraph(para) = self.tree.cur_mut() {
+                para.children.push(raw_line(&src, line, true));
+                return;
             } else {
                 // unless the top of the stack was not a paragraph
                 // in which case, we revert these changes
                 for _ in 0..dropped {
-                    tree.pop();
+                    self.tree.pop();
                 }
             }
         }
 
         // Indented code
-        match (scan_indented_code(line), tree.cur_mut()) {
+        match (scan_indented_code(line), self.tree.cur_mut()) {
             (true, Code(code)) => {
-                while let Some(sep) = chunk_separators.pop() {
+                while let Some(sep) = self.chunk_separators.pop() {
                     code.text.push_str(&sep);
                 }
                 let mut line = Line::new(line);
                 line.scan_space_upto(4);
                 code.text.push_str(&format!("{}\n", line.remainder()));
-                continue;
+                return;
             }
             (true, _) => {
-                chunk_separators.clear();
+                self.chunk_separators.clear();
+                let mut code = ast::Code::new();
+                code.indent = 4;
+                code.position = src.span(line);
                 let mut line = Line::new(line);
                 line.scan_space_upto(4);
                 let content = line.remainder();
-                let mut code = ast::Code::new();
                 code.text = format!("{}{content}\n", &"    "[.._remaining_space]);
-                tree.push(Code(code));
-                continue;
+                self.tree.push(Code(code));
+                return;
             }
             _ => {}
         }
 
-        if let (Code(_), None) = (tree.cur_mut(), &code_block_type) {
-            tree.pop();
+        if let Code(ast::Code {
+            block_type: Indented,
+            ..
+        }) = self.tree.cur_mut()
+        {
+            self.tree.pop();
         }
 
-        if let List(_) = tree.cur_mut() {
-            tree.pop();
+        if let List(_) = self.tree.cur_mut() {
+            self.tree.pop();
         }
 
-        let mut para = Paragraph(Paragraph::new());
+        let mut para = Paragraph(Paragraph::new()).with_position(src.span(line.trim_start()));
         para.children_mut()
             .unwrap()
-            .push(Text(line.trim_start().into()));
-        tree.push(para);
+            .push(raw_line(&src, line, false));
+        self.tree.push(para);
     }
-
-    tighten(&mut tree.root);
-    format!("{}", tree.root)
 }
 
 fn matched_containers<'a>(
@@ -736,6 +1235,26 @@ fn matched_containers<'a>(
                     // not sure, defer to list item
                 }
             },
+            FootnoteDefinition(definition) => {
+                if line.trim().is_empty() {
+                    i += 1;
+                    continue;
+                }
+                let width =
+                    line.chars()
+                        .take_while(|ch| matches!(ch, ' ' | '\t'))
+                        .fold(0, |width, ch| match ch {
+                            '\t' => width + 4 - width % 4,
+                            _ => width + 1,
+                        });
+                if width < definition.indent {
+                    break;
+                }
+                let mut lin = Line::new(line);
+                lin.scan_space_upto(definition.indent);
+                remaining_spaces = lin.remaining_spaces;
+                line = lin.remainder();
+            }
             ListItem(list_item) => {
                 if line.trim().is_empty() {
                     if list_item.children.is_empty() {
@@ -747,7 +1266,7 @@ fn matched_containers<'a>(
                 }
 
                 let indent = list_item.indent;
-                let mut chars = line.chars();
+                let chars = line.chars();
                 let mut count = 0;
                 let mut used = 0;
 
@@ -756,26 +1275,21 @@ fn matched_containers<'a>(
                     remaining_spaces -= 1;
                 }
 
-                loop {
-                    match chars.next() {
-                        Some(ch) => {
-                            if ch == ' ' {
-                                count += 1;
-                                used += 1;
-                                if count >= indent {
-                                    break;
-                                }
-                            } else if ch == '\t' {
-                                count = (count / 4) + 4;
-                                used += 1;
-                                if count >= indent {
-                                    break;
-                                }
-                            } else {
-                                break;
-                            }
+                for ch in chars {
+                    if ch == ' ' {
+                        count += 1;
+                        used += 1;
+                        if count >= indent {
+                            break;
                         }
-                        None => break,
+                    } else if ch == '\t' {
+                        count = (count / 4) + 4;
+                        used += 1;
+                        if count >= indent {
+                            break;
+                        }
+                    } else {
+                        break;
                     }
                 }
 
@@ -823,6 +1337,374 @@ fn matched_containers<'a>(
     (i, line, remaining_spaces)
 }
 
+/// Remove link reference definitions from the start of paragraphs and
+/// collect them, along with the labels of footnote definitions. Paragraphs
+/// that contain only definitions are removed.
+fn collect_definitions(node: &mut Node, definitions: &mut Definitions) {
+    let children = match node.children_mut() {
+        Some(children) => children,
+        None => return,
+    };
+    for child in children.iter_mut() {
+        if let Paragraph(para) = child {
+            let text = raw_text(&para.children);
+            let len = scan_definitions(&text, definitions);
+            let (_, rest) = split_raw(std::mem::take(&mut para.children), len);
+            if let Some(first) = rest.first() {
+                para.position.start = first.position().start;
+            }
+            para.children = rest;
+        } else {
+            if let FootnoteDefinition(definition) = child {
+                let label = inline::normalize_label(&definition.label);
+                definitions.footnotes.insert(label);
+            }
+            collect_definitions(child, definitions);
+        }
+    }
+    children.retain(|c| !matches!(c, Paragraph(para) if para.children.is_empty()));
+}
+
+/// Scan the link reference definitions at the start of a text, adding any
+/// new labels to the definitions. Returns the number of bytes consumed.
+fn scan_definitions(text: &str, definitions: &mut Definitions) -> usize {
+    let mut position = 0;
+    while let Some((label, definition, len)) = inline::scan_definition(&text[position..]) {
+        definitions
+            .links
+            .entry(inline::normalize_label(label))
+            .or_insert(definition);
+        position += len;
+    }
+    position
+}
+
+/// Remove task list item markers from the start of the first paragraph of
+/// list items, recording whether the items are checked.
+fn resolve_tasks(node: &mut Node) {
+    if let ListItem(item) = node {
+        if let Some(Paragraph(para)) = item.children.first_mut() {
+            if let Some((checked, len)) = scan_task_marker(&raw_text(&para.children)) {
+                let (_, rest) = split_raw(std::mem::take(&mut para.children), len);
+                para.position.start = rest[0].position().start;
+                para.children = rest;
+                item.checked = Some(checked);
+            }
+        }
+    }
+    if let Some(children) = node.children_mut() {
+        for child in children {
+            resolve_tasks(child);
+        }
+    }
+}
+
+/// Scan a task list item marker and the whitespace after it. Returns whether
+/// the task is checked and the number of bytes consumed.
+fn scan_task_marker(text: &str) -> Option<(bool, usize)> {
+    let checked = match text.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    let rest = &text[3..];
+    let content = rest.trim_start();
+    // the marker must be followed by whitespace and then content
+    if content.is_empty() || content.len() == rest.len() {
+        return None;
+    }
+    Some((checked, text.len() - content.len()))
+}
+
+/// Parse the raw text of paragraphs and headings into inline content.
+fn resolve_inlines(node: &mut Node, definitions: &Definitions, options: &Options) {
+    match node {
+        Paragraph(ast::Paragraph { children, .. })
+        | Heading(Heading { children, .. })
+        | TableCell(ast::TableCell { children, .. }) => {
+            let map = source_map(children);
+            *children = inline::parse(raw_text(children).trim_end(), definitions, &map, options);
+        }
+        _ => {
+            if let Some(children) = node.children_mut() {
+                for child in children {
+                    resolve_inlines(child, definitions, options);
+                }
+            }
+        }
+    }
+}
+
+/// Give headings without an ID one made from their text, which is unique
+/// among the IDs given so far.
+fn resolve_heading_ids(node: &mut Node, slugger: &Slugger, ids: &mut HashMap<String, usize>) {
+    if let Heading(heading) = node {
+        match &heading.id {
+            // explicit IDs are kept, and generated IDs avoid them
+            Some(id) => {
+                ids.entry(id.clone()).or_default();
+            }
+            None => {
+                let text: String = heading.children.iter().map(Node::plain_text).collect();
+                heading.id = Some(unique_id(slugger.slug(&text), ids));
+            }
+        }
+    } else if let Some(children) = node.children_mut() {
+        for child in children {
+            resolve_heading_ids(child, slugger, ids);
+        }
+    }
+}
+
+/// Split a trailing attribute block, such as `{#id .class key=value}`, from
+/// text if attribute blocks are enabled. Returns the text before the block
+/// and the attributes in it.
+fn split_attributes<'a>(text: &'a str, options: &Options) -> Option<(&'a str, Attributes)> {
+    if !options.attributes {
+        return None;
+    }
+    let inner = text.trim_end().strip_suffix('}')?;
+    if is_escaped(inner) {
+        return None;
+    }
+    let start = inner.rfind('{')?;
+    let block = &inner[start + 1..];
+    if block.contains('}') || is_escaped(&inner[..start]) {
+        return None;
+    }
+
+    let mut attributes = Attributes::new();
+    for cap in ATTRIBUTE_RE.captures_iter(block) {
+        if let Some(name) = cap.get(1) {
+            let value = cap.get(2).or(cap.get(3)).map_or("", |m| m.as_str());
+            // an `id` or `class` given by name is merged with `#id` and
+            // `.class`, so that each attribute is only written once
+            match name.as_str() {
+                "id" => attributes.id = Some(value.into()),
+                "class" => attributes
+                    .classes
+                    .extend(value.split_whitespace().map(String::from)),
+                name => attributes.attrs.push((name.into(), value.into())),
+            }
+            continue;
+        }
+        // anything other than an ID or a class is ignored
+        let token = &cap[4];
+        if let Some(id) = token.strip_prefix('#').filter(|id| !id.is_empty()) {
+            attributes.id = Some(id.into());
+        } else if let Some(class) = token.strip_prefix('.').filter(|c| !c.is_empty()) {
+            attributes.classes.push(class.into());
+        }
+    }
+    Some((inner[..start].trim_end(), attributes))
+}
+
+/// Returns whether the character following some text is escaped, that is,
+/// whether the text ends with an odd number of backslashes.
+fn is_escaped(text: &str) -> bool {
+    text.bytes().rev().take_while(|b| *b == b'\\').count() % 2 == 1
+}
+
+/// Strip the closing sequence of an ATX heading from its content.
+fn strip_closing_sequence(content: &str) -> &str {
+    let rest = content.trim_end_matches('#');
+    match rest.len() < content.len() && (rest.is_empty() || rest.ends_with([' ', '\t'])) {
+        true => rest.trim_end(),
+        false => content,
+    }
+}
+
+/// Returns an ID for a slug that is unique among the IDs given so far, and
+/// records it.
+fn unique_id(slug: String, ids: &mut HashMap<String, usize>) -> String {
+    // repeated slugs are numbered, skipping IDs that are already used
+    let mut id = slug.clone();
+    while ids.contains_key(&id) {
+        let count = ids.entry(slug.clone()).or_default();
+        *count += 1;
+        id = format!("{slug}-{count}");
+    }
+    ids.insert(id.clone(), 0);
+    id
+}
+
+/// Returns the raw text of a paragraph or heading before inline parsing.
+fn raw_text(children: &[Node]) -> String {
+    children
+        .iter()
+        .map(|c| match c {
+            Text(text) => text.text.as_str(),
+            _ => "",
+        })
+        .collect()
+}
+
+/// Returns a raw text node for a line of a paragraph. Continuation lines are
+/// prefixed with a newline.
+fn raw_line(src: &SourceLine, line: &str, continuation: bool) -> Node {
+    let content = line.trim_start();
+    let text = match continuation {
+        true => format!("\n{content}"),
+        false => content.to_string(),
+    };
+    Text(ast::Text::new(text)).with_position(src.span(content))
+}
+
+/// Split the raw text of a paragraph or heading at a byte offset.
+fn split_raw(children: Vec<Node>, at: usize) -> (Vec<Node>, Vec<Node>) {
+    let (mut head, mut tail) = (vec![], vec![]);
+    let mut offset = 0;
+    for child in children {
+        let len = match &child {
+            Text(text) => text.text.len(),
+            _ => 0,
+        };
+        if offset + len <= at {
+            head.push(child);
+        } else if offset >= at {
+            tail.push(child);
+        } else if let Text(text) = child {
+            // the position of a line starts after its leading newline
+            let k = at - offset;
+            let lead = usize::from(text.text.starts_with('\n'));
+            let point = text.position.start.add(k.saturating_sub(lead));
+            let (before, after) = text.text.split_at(k);
+            let before = ast::Text::new(before.to_string());
+            let after = ast::Text::new(after.to_string());
+            head.push(Text(before).with_position(Position::new(text.position.start, point)));
+            tail.push(Text(after).with_position(Position::new(point, text.position.end)));
+        }
+        offset += len;
+    }
+    (head, tail)
+}
+
+/// Returns the source map for the raw text of a paragraph or heading.
+fn source_map(children: &[Node]) -> SourceMap {
+    let mut map = SourceMap::new();
+    let mut offset = 0;
+    for child in children {
+        if let Text(text) = child {
+            let lead = usize::from(text.text.starts_with('\n'));
+            map.push(offset + lead, text.position.start);
+            offset += text.text.len();
+        }
+    }
+    map
+}
+
+/// Returns the column alignments of a table delimiter row, if the line is
+/// one. Delimiter rows must contain a pipe, so that they are not confused with
+/// setext heading underlines.
+fn table_alignments(line: &str) -> Option<Vec<Alignment>> {
+    let cells = split_row(line);
+    // a table has at least one column
+    if !line.contains('|') || cells.is_empty() {
+        return None;
+    }
+    cells
+        .into_iter()
+        .map(|(start, end)| {
+            let cell = &line[start..end];
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.bytes().all(|b| b == b'-') {
+                return None;
+            }
+            let alignment = match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => Alignment::Center,
+                (true, false) => Alignment::Left,
+                (false, true) => Alignment::Right,
+                (false, false) => Alignment::None,
+            };
+            Some(alignment)
+        })
+        .collect()
+}
+
+/// Returns the cells of a table row that starts at a point, with the raw text
+/// of their content. Escaped pipes are unescaped, including in code spans.
+fn table_cells(row: &str, start: Point) -> Vec<Node> {
+    let text = |from: usize, to: usize| {
+        let text = Text(ast::Text::new(row[from..to].to_string()));
+        text.with_position(Position::new(start.add(from), start.add(to)))
+    };
+    split_row(row)
+        .into_iter()
+        .map(|(from, to)| {
+            let mut children = vec![];
+            let mut piece = from;
+            for (i, _) in row[from..to].match_indices("\\|") {
+                if piece < from + i {
+                    children.push(text(piece, from + i));
+                }
+                // keep the pipe and drop the backslash
+                piece = from + i + 1;
+            }
+            if piece < to {
+                children.push(text(piece, to));
+            }
+            let cell = TableCell(ast::TableCell::new(children));
+            cell.with_position(Position::new(start.add(from), start.add(to)))
+        })
+        .collect()
+}
+
+/// Split a table row at unescaped pipes into the byte ranges of its cells,
+/// trimmed of whitespace. Leading and trailing pipes are optional.
+fn split_row(row: &str) -> Vec<(usize, usize)> {
+    let mut pipes = vec![];
+    let mut escaped = false;
+    for (i, b) in row.bytes().enumerate() {
+        match b {
+            _ if escaped => escaped = false,
+            b'\\' => escaped = true,
+            b'|' => pipes.push(i),
+            _ => {}
+        }
+    }
+    let mut cells = vec![];
+    let mut start = 0;
+    for pipe in &pipes {
+        cells.push((start, *pipe));
+        start = pipe + 1;
+    }
+    cells.push((start, row.len()));
+    if let (Some(first), Some(last)) = (pipes.first(), pipes.last()) {
+        if row[last + 1..].trim().is_empty() {
+            cells.pop();
+        }
+        if row[..*first].trim().is_empty() {
+            cells.remove(0);
+        }
+    }
+    cells
+        .into_iter()
+        .map(|(start, end)| {
+            let cell = &row[start..end];
+            let start = start + cell.len() - cell.trim_start().len();
+            (start, start + cell.trim().len())
+        })
+        .collect()
+}
+
+/// Returns whether a line starts a block that ends a table.
+fn interrupts_table(line: &str) -> bool {
+    HTML_1_5_RE.is_match(line)
+        || HTML_6_RE.is_match(line)
+        || ATX_HEADING_RE.is_match(line)
+        || HR_RE.is_match(line)
+        || FENCED_CODE_RE.is_match(line)
+}
+
+/// Returns the kind of front matter that a line delimits, if any.
+fn front_matter_kind(line: &str) -> Option<FrontMatterKind> {
+    match line.trim_end() {
+        "---" => Some(FrontMatterKind::Yaml),
+        "+++" => Some(FrontMatterKind::Toml),
+        _ => None,
+    }
+}
+
 fn tighten(node: &mut Node) {
     if let List(ast::List {
         proximity: ListProximity::Tight,
@@ -878,9 +1760,9 @@ fn end_condition_met(html: &Html, line: &str) -> bool {
 }
 
 fn scan_indented_code(line: &str) -> bool {
-    let mut chars = line.chars();
+    let chars = line.chars();
     let mut spaces = 0;
-    while let Some(ch) = chars.next() {
+    for ch in chars {
         match ch {
             ' ' => spaces += 1,
             '\t' => spaces += 4 - (spaces % 4),
@@ -890,6 +1772,44 @@ fn scan_indented_code(line: &str) -> bool {
     spaces >= 4
 }
 
+/// Line of the source text, used to compute source positions.
+struct SourceLine<'a> {
+    /// Line text, without the line ending.
+    text: &'a str,
+    /// Line number, starting at 1.
+    number: usize,
+    /// Byte offset of the line in the source text.
+    offset: usize,
+}
+
+impl<'a> SourceLine<'a> {
+    /// Creates a new SourceLine object.
+    fn new(text: &'a str, number: usize, offset: usize) -> Self {
+        Self {
+            text,
+            number,
+            offset,
+        }
+    }
+
+    /// Returns the point at the start of a slice of the line.
+    fn point(&self, slice: &str) -> Point {
+        let index = slice.as_ptr() as usize - self.text.as_ptr() as usize;
+        debug_assert!(index <= self.text.len(), "slice should be part of the line");
+        Point::new(self.number, index + 1, self.offset + index)
+    }
+
+    /// Returns the position of a slice of the line.
+    fn span(&self, slice: &str) -> Position {
+        Position::new(self.point(slice), self.point(&slice[slice.len()..]))
+    }
+
+    /// Returns the point at the end of the line.
+    fn end(&self) -> Point {
+        self.point(&self.text[self.text.len()..])
+    }
+}
+
 /// Abstraction over a line of text.
 struct Line<'a> {
     /// Input text.
@@ -912,9 +1832,9 @@ impl<'a> Line<'a> {
 
     /// Scan up to the specified number of spaces.
     fn scan_space_upto(&mut self, n: usize) {
-        let mut chars = self.input.chars();
+        let chars = self.input.chars();
         let mut spaces = 0;
-        while let Some(ch) = chars.next() {
+        for ch in chars {
             if spaces >= n {
                 break;
             }
@@ -0,0 +1,252 @@
+//! Parsing and rendering options.
+
+use std::{
+    fmt,
+    panic::{RefUnwindSafe, UnwindSafe},
+    sync::Arc,
+};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+
+/// Soft line break rendering.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SoftBreak {
+    /// Render soft line breaks as newlines.
+    #[default]
+    Newline,
+    /// Render soft line breaks as spaces.
+    Space,
+    /// Render soft line breaks as hard line breaks.
+    Break,
+}
+
+/// Function that turns the text of a heading into its ID.
+///
+/// The default slugger follows GitHub: the text is lowercased, characters
+/// other than letters, numbers, underscores, hyphens and spaces are removed,
+/// and spaces are replaced with hyphens. IDs are made unique afterwards by
+/// appending `-1`, `-2` and so on to repeated slugs.
+///
+/// # Examples
+///
+/// ```
+/// use markdown::{Options, Slugger};
+///
+/// let slugger = Slugger::new(|text| text.to_uppercase().replace(' ', "_"));
+/// let options = Options::new().heading_ids(true).slugger(slugger);
+/// let html = markdown::to_html_with_options("# foo bar", &options);
+/// assert_eq!(html, "<h1 id=\"FOO_BAR\">foo bar</h1>\n");
+/// ```
+#[derive(Clone)]
+pub struct Slugger(Arc<dyn Fn(&str) -> String + Send + Sync + RefUnwindSafe>);
+
+impl Slugger {
+    /// Create a slugger from a function.
+    pub fn new(slug: impl Fn(&str) -> String + Send + Sync + RefUnwindSafe + 'static) -> Self {
+        Self(Arc::new(slug))
+    }
+
+    /// Returns the slug for the text of a heading.
+    pub fn slug(&self, text: &str) -> String {
+        (self.0)(text)
+    }
+}
+
+impl Default for Slugger {
+    fn default() -> Self {
+        Self::new(github_slug)
+    }
+}
+
+impl fmt::Debug for Slugger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slugger").finish_non_exhaustive()
+    }
+}
+
+lazy_static! {
+    // characters that GitHub removes from slugs
+    static ref SLUG_REMOVED_RE: Regex =
+        Regex::new(r"[^\p{L}\p{M}\p{N}\p{Pc}\ \-]").expect("slug regex should be valid");
+}
+
+/// Returns the GitHub slug for the text of a heading.
+fn github_slug(text: &str) -> String {
+    SLUG_REMOVED_RE
+        .replace_all(&text.to_lowercase(), "")
+        .replace(' ', "-")
+}
+
+/// Parsing and rendering options.
+///
+/// The defaults follow CommonMark, except that raw HTML and potentially
+/// dangerous URLs are not rendered unless [`Options::unsafe_html`] is set.
+/// Options can be set directly or with the builder methods.
+///
+/// # Examples
+///
+/// ```
+/// use markdown::{Options, SoftBreak};
+///
+/// let options = Options::new()
+///     .soft_break(SoftBreak::Space)
+///     .source_positions(true);
+/// let html = markdown::to_html_with_options("hello\nworld", &options);
+/// assert_eq!(html, "<p data-sourcepos=\"1:1-2:5\">hello world</p>\n");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// How to render soft line breaks.
+    pub soft_break: SoftBreak,
+    /// Whether to add `data-sourcepos` attributes to block elements.
+    pub source_positions: bool,
+    /// Whether to render raw HTML and links and images with potentially
+    /// dangerous URLs, such as `javascript:` URLs. Otherwise raw HTML is
+    /// omitted and such URLs are rendered as empty.
+    pub unsafe_html: bool,
+    /// Whether to render raw HTML as escaped text instead of omitting it,
+    /// when unsafe HTML is not rendered.
+    pub escape_html: bool,
+    /// Whether to escape the tags that GFM disallows in raw HTML, such as
+    /// `<script>` and `<iframe>`, when unsafe HTML is rendered.
+    pub tagfilter: bool,
+    /// Whether to parse GFM tables.
+    pub tables: bool,
+    /// Whether to parse GFM task list items.
+    pub tasklist: bool,
+    /// Whether to render task list checkboxes enabled, with a
+    /// `data-task-index` attribute that counts the tasks in the document from
+    /// zero, instead of disabled.
+    pub interactive_tasklist: bool,
+    /// Whether to parse GFM strikethrough.
+    pub strikethrough: bool,
+    /// Whether to turn GFM extended autolinks, such as `www.example.com` and
+    /// bare URLs and email addresses, into links.
+    pub autolink: bool,
+    /// Whether to parse footnote definitions and references.
+    pub footnotes: bool,
+    /// Whether to parse YAML or TOML front matter at the start of the input.
+    pub front_matter: bool,
+    /// Whether to add `id` attributes to headings, made from their text by
+    /// the slugger and unique within the document.
+    pub heading_ids: bool,
+    /// Whether to add an anchor that links to the heading itself at the start
+    /// of each heading with an ID.
+    pub heading_anchors: bool,
+    /// How heading IDs are made from the text of headings.
+    pub slugger: Slugger,
+    /// Whether to parse attribute blocks, such as `{#id .class key=value}`,
+    /// at the end of headings and fenced code block info strings.
+    pub attributes: bool,
+}
+
+// options can be used inside `catch_unwind`, to contain a panic while
+// rendering untrusted input
+const _: fn() = || {
+    fn assert_unwind_safe<T: UnwindSafe + RefUnwindSafe>() {}
+    assert_unwind_safe::<Options>();
+};
+
+impl Options {
+    /// Create the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how soft line breaks are rendered.
+    pub fn soft_break(mut self, soft_break: SoftBreak) -> Self {
+        self.soft_break = soft_break;
+        self
+    }
+
+    /// Set whether to add `data-sourcepos` attributes to block elements.
+    pub fn source_positions(mut self, source_positions: bool) -> Self {
+        self.source_positions = source_positions;
+        self
+    }
+
+    /// Set whether to render raw HTML and potentially dangerous URLs.
+    pub fn unsafe_html(mut self, unsafe_html: bool) -> Self {
+        self.unsafe_html = unsafe_html;
+        self
+    }
+
+    /// Set whether to escape raw HTML instead of omitting it.
+    pub fn escape_html(mut self, escape_html: bool) -> Self {
+        self.escape_html = escape_html;
+        self
+    }
+
+    /// Set whether to escape the tags that GFM disallows in raw HTML.
+    pub fn tagfilter(mut self, tagfilter: bool) -> Self {
+        self.tagfilter = tagfilter;
+        self
+    }
+
+    /// Set whether to parse GFM tables.
+    pub fn tables(mut self, tables: bool) -> Self {
+        self.tables = tables;
+        self
+    }
+
+    /// Set whether to parse GFM task list items.
+    pub fn tasklist(mut self, tasklist: bool) -> Self {
+        self.tasklist = tasklist;
+        self
+    }
+
+    /// Set whether to render task list checkboxes enabled and indexed.
+    pub fn interactive_tasklist(mut self, interactive_tasklist: bool) -> Self {
+        self.interactive_tasklist = interactive_tasklist;
+        self
+    }
+
+    /// Set whether to parse GFM strikethrough.
+    pub fn strikethrough(mut self, strikethrough: bool) -> Self {
+        self.strikethrough = strikethrough;
+        self
+    }
+
+    /// Set whether to parse GFM extended autolinks.
+    pub fn autolink(mut self, autolink: bool) -> Self {
+        self.autolink = autolink;
+        self
+    }
+
+    /// Set whether to parse footnotes.
+    pub fn footnotes(mut self, footnotes: bool) -> Self {
+        self.footnotes = footnotes;
+        self
+    }
+
+    /// Set whether to parse front matter.
+    pub fn front_matter(mut self, front_matter: bool) -> Self {
+        self.front_matter = front_matter;
+        self
+    }
+
+    /// Set whether to add `id` attributes to headings.
+    pub fn heading_ids(mut self, heading_ids: bool) -> Self {
+        self.heading_ids = heading_ids;
+        self
+    }
+
+    /// Set whether to add self-link anchors to headings with IDs.
+    pub fn heading_anchors(mut self, heading_anchors: bool) -> Self {
+        self.heading_anchors = heading_anchors;
+        self
+    }
+
+    /// Set how heading IDs are made from the text of headings.
+    pub fn slugger(mut self, slugger: Slugger) -> Self {
+        self.slugger = slugger;
+        self
+    }
+
+    /// Set whether to parse attribute blocks on headings and code blocks.
+    pub fn attributes(mut self, attributes: bool) -> Self {
+        self.attributes = attributes;
+        self
+    }
+}
@@ -0,0 +1,171 @@
+//! Table of contents.
+//!
+//! [`Toc`] collects the headings of a syntax tree into a nested outline,
+//! which can be rendered as nested lists of links to the headings. The links
+//! use the IDs that the headings were given when the tree was parsed, so
+//! [`Options::heading_ids`](crate::Options::heading_ids) or attribute blocks
+//! should be enabled.
+//!
+//! # Examples
+//!
+//! ```
+//! use markdown::{toc::Toc, Options};
+//!
+//! let options = Options::new().heading_ids(true);
+//! let mut root = markdown::parse_with_options("[[toc]]\n\n# foo\n\n## bar", &options);
+//! let toc = Toc::new(&root);
+//! assert_eq!(toc.entries[0].id.as_deref(), Some("foo"));
+//! assert_eq!(toc.entries[0].children[0].text, "bar");
+//!
+//! toc.replace_marker(&mut root);
+//! assert!(root.to_string().starts_with("<ul>\n<li><a href=\"#foo\">foo</a>"));
+//! ```
+
+use crate::ast::{self, ListType, Node, Position};
+
+/// Text of the paragraph that [`Toc::replace_marker`] replaces.
+pub const MARKER: &str = "[[toc]]";
+
+/// Heading in a table of contents.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TocEntry {
+    pub level: u8,
+    /// The text of the heading without markup.
+    pub text: String,
+    /// The ID of the heading, if it has one.
+    pub id: Option<String>,
+    pub position: Position,
+    /// The headings under this one.
+    pub children: Vec<TocEntry>,
+}
+
+/// Table of contents of a syntax tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Toc {
+    /// The top-level headings.
+    pub entries: Vec<TocEntry>,
+}
+
+impl Toc {
+    /// Build the table of contents of all headings under a node.
+    pub fn new(node: &Node) -> Self {
+        Self::with_depth(node, 1, 6)
+    }
+
+    /// Build the table of contents of the headings under a node with levels
+    /// from `min` to `max`.
+    ///
+    /// Each heading is nested under the closest heading before it with a
+    /// lower level, so skipped levels do not add empty entries.
+    pub fn with_depth(node: &Node, min: u8, max: u8) -> Self {
+        let mut headings = vec![];
+        collect_headings(node, &mut headings);
+
+        // open entries, with the innermost last
+        let mut stack: Vec<TocEntry> = vec![];
+        let mut entries = vec![];
+        for heading in headings {
+            if heading.level < min || heading.level > max {
+                continue;
+            }
+            let text: String = heading.children.iter().map(Node::plain_text).collect();
+            let entry = TocEntry {
+                level: heading.level,
+                text: text.replace('\n', " "),
+                id: heading.id.clone(),
+                position: heading.position,
+                children: vec![],
+            };
+            while stack.last().is_some_and(|last| last.level >= entry.level) {
+                close_entry(&mut stack, &mut entries);
+            }
+            stack.push(entry);
+        }
+        while !stack.is_empty() {
+            close_entry(&mut stack, &mut entries);
+        }
+        Self { entries }
+    }
+
+    /// Returns the table of contents as nested lists of links to the
+    /// headings. Headings without IDs are listed without links.
+    pub fn to_node(&self) -> Node {
+        links(&self.entries)
+    }
+
+    /// Render the table of contents as nested HTML lists of links to the
+    /// headings.
+    pub fn to_html(&self) -> String {
+        match self.entries.is_empty() {
+            true => "".to_string(),
+            false => self.to_node().to_string(),
+        }
+    }
+
+    /// Replace the paragraphs under a node that contain only the
+    /// [`MARKER`] with the table of contents, or remove them if it is empty.
+    pub fn replace_marker(&self, node: &mut Node) {
+        let Some(children) = node.children_mut() else {
+            return;
+        };
+        if self.entries.is_empty() {
+            children.retain(|c| !matches!(c, Node::Paragraph(para) if is_marker(&para.children)));
+        }
+        for child in children {
+            match child {
+                Node::Paragraph(para) if is_marker(&para.children) => {
+                    let position = para.position;
+                    *child = self.to_node().with_position(position);
+                }
+                _ => self.replace_marker(child),
+            }
+        }
+    }
+}
+
+/// Collect the headings under a node in document order.
+fn collect_headings<'a>(node: &'a Node, headings: &mut Vec<&'a ast::Heading>) {
+    match node {
+        Node::Heading(heading) => headings.push(heading),
+        _ => {
+            for child in node.children().into_iter().flatten() {
+                collect_headings(child, headings);
+            }
+        }
+    }
+}
+
+/// Close the innermost open entry, adding it to its parent.
+fn close_entry(stack: &mut Vec<TocEntry>, entries: &mut Vec<TocEntry>) {
+    if let Some(entry) = stack.pop() {
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(entry),
+            None => entries.push(entry),
+        }
+    }
+}
+
+/// Returns whether the content of a paragraph is the table of contents
+/// marker.
+fn is_marker(children: &[Node]) -> bool {
+    let text: String = children.iter().map(Node::plain_text).collect();
+    children.iter().all(|c| matches!(c, Node::Text(_))) && text.trim() == MARKER
+}
+
+/// Returns a tight list of links to the headings of entries.
+fn links(entries: &[TocEntry]) -> Node {
+    let mut list = ast::List::new(ListType::Unordered('-'));
+    for entry in entries {
+        let text = Node::Text(ast::Text::new(entry.text.clone()));
+        let mut item = ast::ListItem::new(2);
+        item.children.push(match &entry.id {
+            Some(id) => Node::Link(ast::Link::new(format!("#{id}"), None, vec![text])),
+            None => text,
+        });
+        if !entry.children.is_empty() {
+            item.children.push(links(&entry.children));
+        }
+        list.children.push(Node::ListItem(item));
+    }
+    Node::List(list)
+}
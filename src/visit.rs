@@ -0,0 +1,468 @@
+//! Syntax tree traversal.
+//!
+//! [`Visitor`] and [`VisitorMut`] walk a tree by reference, with a hook for
+//! each type of node. [`Fold`] consumes a tree and rebuilds it, allowing nodes
+//! to be replaced, removed, or spliced into their parent.
+//!
+//! Every hook walks the children of its node by default. A hook that is
+//! overridden can continue the walk with [`walk_children`],
+//! [`walk_children_mut`], or [`fold_children`].
+//!
+//! # Examples
+//!
+//! ```
+//! use markdown::ast::Heading;
+//! use markdown::visit::{walk_children_mut, VisitorMut};
+//!
+//! struct Demote;
+//!
+//! impl VisitorMut for Demote {
+//!     fn visit_heading_mut(&mut self, heading: &mut Heading) {
+//!         heading.level = (heading.level + 1).min(6);
+//!         walk_children_mut(self, &mut heading.children);
+//!     }
+//! }
+//!
+//! let mut root = markdown::parse("# foo");
+//! Demote.visit_node_mut(&mut root);
+//! assert_eq!(root.to_string(), "<h2>foo</h2>\n");
+//! ```
+
+use crate::ast::{
+    Autolink, BlockQuote, Code, CodeSpan, Emphasis, FootnoteDefinition, FootnoteReference,
+    FrontMatter, Heading, Html, Image, InlineHtml, LineBreak, Link, List, ListItem, Node,
+    Paragraph, Root, Strikethrough, Strong, Table, TableCell, TableRow, Text, ThematicBreak,
+};
+
+/// Visitor over a syntax tree.
+pub trait Visitor {
+    /// Visit a node, dispatching to the hook for its type.
+    fn visit_node(&mut self, node: &Node) {
+        walk_node(self, node);
+    }
+
+    fn visit_root(&mut self, root: &Root) {
+        walk_children(self, &root.children);
+    }
+
+    fn visit_block_quote(&mut self, block_quote: &BlockQuote) {
+        walk_children(self, &block_quote.children);
+    }
+
+    fn visit_list(&mut self, list: &List) {
+        walk_children(self, &list.children);
+    }
+
+    fn visit_list_item(&mut self, list_item: &ListItem) {
+        walk_children(self, &list_item.children);
+    }
+
+    fn visit_footnote_definition(&mut self, footnote_definition: &FootnoteDefinition) {
+        walk_children(self, &footnote_definition.children);
+    }
+
+    fn visit_front_matter(&mut self, _front_matter: &FrontMatter) {}
+
+    fn visit_thematic_break(&mut self, _thematic_break: &ThematicBreak) {}
+
+    fn visit_heading(&mut self, heading: &Heading) {
+        walk_children(self, &heading.children);
+    }
+
+    fn visit_paragraph(&mut self, paragraph: &Paragraph) {
+        walk_children(self, &paragraph.children);
+    }
+
+    fn visit_code(&mut self, _code: &Code) {}
+
+    fn visit_html(&mut self, _html: &Html) {}
+
+    fn visit_table(&mut self, table: &Table) {
+        walk_children(self, &table.children);
+    }
+
+    fn visit_table_row(&mut self, table_row: &TableRow) {
+        walk_children(self, &table_row.children);
+    }
+
+    fn visit_table_cell(&mut self, table_cell: &TableCell) {
+        walk_children(self, &table_cell.children);
+    }
+
+    fn visit_code_span(&mut self, _code_span: &CodeSpan) {}
+
+    fn visit_emphasis(&mut self, emphasis: &Emphasis) {
+        walk_children(self, &emphasis.children);
+    }
+
+    fn visit_strong(&mut self, strong: &Strong) {
+        walk_children(self, &strong.children);
+    }
+
+    fn visit_strikethrough(&mut self, strikethrough: &Strikethrough) {
+        walk_children(self, &strikethrough.children);
+    }
+
+    fn visit_link(&mut self, link: &Link) {
+        walk_children(self, &link.children);
+    }
+
+    fn visit_image(&mut self, image: &Image) {
+        walk_children(self, &image.children);
+    }
+
+    fn visit_autolink(&mut self, _autolink: &Autolink) {}
+
+    fn visit_footnote_reference(&mut self, _footnote_reference: &FootnoteReference) {}
+
+    fn visit_inline_html(&mut self, _inline_html: &InlineHtml) {}
+
+    fn visit_soft_break(&mut self, _soft_break: &LineBreak) {}
+
+    fn visit_hard_break(&mut self, _hard_break: &LineBreak) {}
+
+    fn visit_text(&mut self, _text: &Text) {}
+}
+
+/// Call the hook of a visitor for the type of a node.
+pub fn walk_node<V: Visitor + ?Sized>(visitor: &mut V, node: &Node) {
+    match node {
+        Node::Root(x) => visitor.visit_root(x),
+        Node::BlockQuote(x) => visitor.visit_block_quote(x),
+        Node::List(x) => visitor.visit_list(x),
+        Node::ListItem(x) => visitor.visit_list_item(x),
+        Node::FootnoteDefinition(x) => visitor.visit_footnote_definition(x),
+        Node::FrontMatter(x) => visitor.visit_front_matter(x),
+        Node::ThematicBreak(x) => visitor.visit_thematic_break(x),
+        Node::Heading(x) => visitor.visit_heading(x),
+        Node::Paragraph(x) => visitor.visit_paragraph(x),
+        Node::Code(x) => visitor.visit_code(x),
+        Node::Html(x) => visitor.visit_html(x),
+        Node::Table(x) => visitor.visit_table(x),
+        Node::TableRow(x) => visitor.visit_table_row(x),
+        Node::TableCell(x) => visitor.visit_table_cell(x),
+        Node::CodeSpan(x) => visitor.visit_code_span(x),
+        Node::Emphasis(x) => visitor.visit_emphasis(x),
+        Node::Strong(x) => visitor.visit_strong(x),
+        Node::Strikethrough(x) => visitor.visit_strikethrough(x),
+        Node::Link(x) => visitor.visit_link(x),
+        Node::Image(x) => visitor.visit_image(x),
+        Node::Autolink(x) => visitor.visit_autolink(x),
+        Node::FootnoteReference(x) => visitor.visit_footnote_reference(x),
+        Node::InlineHtml(x) => visitor.visit_inline_html(x),
+        Node::SoftBreak(x) => visitor.visit_soft_break(x),
+        Node::HardBreak(x) => visitor.visit_hard_break(x),
+        Node::Text(x) => visitor.visit_text(x),
+    }
+}
+
+/// Visit each of a list of nodes in order.
+pub fn walk_children<V: Visitor + ?Sized>(visitor: &mut V, children: &[Node]) {
+    for child in children {
+        visitor.visit_node(child);
+    }
+}
+
+/// Mutable visitor over a syntax tree.
+pub trait VisitorMut {
+    /// Visit a node, dispatching to the hook for its type.
+    fn visit_node_mut(&mut self, node: &mut Node) {
+        walk_node_mut(self, node);
+    }
+
+    fn visit_root_mut(&mut self, root: &mut Root) {
+        walk_children_mut(self, &mut root.children);
+    }
+
+    fn visit_block_quote_mut(&mut self, block_quote: &mut BlockQuote) {
+        walk_children_mut(self, &mut block_quote.children);
+    }
+
+    fn visit_list_mut(&mut self, list: &mut List) {
+        walk_children_mut(self, &mut list.children);
+    }
+
+    fn visit_list_item_mut(&mut self, list_item: &mut ListItem) {
+        walk_children_mut(self, &mut list_item.children);
+    }
+
+    fn visit_footnote_definition_mut(&mut self, footnote_definition: &mut FootnoteDefinition) {
+        walk_children_mut(self, &mut footnote_definition.children);
+    }
+
+    fn visit_front_matter_mut(&mut self, _front_matter: &mut FrontMatter) {}
+
+    fn visit_thematic_break_mut(&mut self, _thematic_break: &mut ThematicBreak) {}
+
+    fn visit_heading_mut(&mut self, heading: &mut Heading) {
+        walk_children_mut(self, &mut heading.children);
+    }
+
+    fn visit_paragraph_mut(&mut self, paragraph: &mut Paragraph) {
+        walk_children_mut(self, &mut paragraph.children);
+    }
+
+    fn visit_code_mut(&mut self, _code: &mut Code) {}
+
+    fn visit_html_mut(&mut self, _html: &mut Html) {}
+
+    fn visit_table_mut(&mut self, table: &mut Table) {
+        walk_children_mut(self, &mut table.children);
+    }
+
+    fn visit_table_row_mut(&mut self, table_row: &mut TableRow) {
+        walk_children_mut(self, &mut table_row.children);
+    }
+
+    fn visit_table_cell_mut(&mut self, table_cell: &mut TableCell) {
+        walk_children_mut(self, &mut table_cell.children);
+    }
+
+    fn visit_code_span_mut(&mut self, _code_span: &mut CodeSpan) {}
+
+    fn visit_emphasis_mut(&mut self, emphasis: &mut Emphasis) {
+        walk_children_mut(self, &mut emphasis.children);
+    }
+
+    fn visit_strong_mut(&mut self, strong: &mut Strong) {
+        walk_children_mut(self, &mut strong.children);
+    }
+
+    fn visit_strikethrough_mut(&mut self, strikethrough: &mut Strikethrough) {
+        walk_children_mut(self, &mut strikethrough.children);
+    }
+
+    fn visit_link_mut(&mut self, link: &mut Link) {
+        walk_children_mut(self, &mut link.children);
+    }
+
+    fn visit_image_mut(&mut self, image: &mut Image) {
+        walk_children_mut(self, &mut image.children);
+    }
+
+    fn visit_autolink_mut(&mut self, _autolink: &mut Autolink) {}
+
+    fn visit_footnote_reference_mut(&mut self, _footnote_reference: &mut FootnoteReference) {}
+
+    fn visit_inline_html_mut(&mut self, _inline_html: &mut InlineHtml) {}
+
+    fn visit_soft_break_mut(&mut self, _soft_break: &mut LineBreak) {}
+
+    fn visit_hard_break_mut(&mut self, _hard_break: &mut LineBreak) {}
+
+    fn visit_text_mut(&mut self, _text: &mut Text) {}
+}
+
+/// Call the hook of a mutable visitor for the type of a node.
+pub fn walk_node_mut<V: VisitorMut + ?Sized>(visitor: &mut V, node: &mut Node) {
+    match node {
+        Node::Root(x) => visitor.visit_root_mut(x),
+        Node::BlockQuote(x) => visitor.visit_block_quote_mut(x),
+        Node::List(x) => visitor.visit_list_mut(x),
+        Node::ListItem(x) => visitor.visit_list_item_mut(x),
+        Node::FootnoteDefinition(x) => visitor.visit_footnote_definition_mut(x),
+        Node::FrontMatter(x) => visitor.visit_front_matter_mut(x),
+        Node::ThematicBreak(x) => visitor.visit_thematic_break_mut(x),
+        Node::Heading(x) => visitor.visit_heading_mut(x),
+        Node::Paragraph(x) => visitor.visit_paragraph_mut(x),
+        Node::Code(x) => visitor.visit_code_mut(x),
+        Node::Html(x) => visitor.visit_html_mut(x),
+        Node::Table(x) => visitor.visit_table_mut(x),
+        Node::TableRow(x) => visitor.visit_table_row_mut(x),
+        Node::TableCell(x) => visitor.visit_table_cell_mut(x),
+        Node::CodeSpan(x) => visitor.visit_code_span_mut(x),
+        Node::Emphasis(x) => visitor.visit_emphasis_mut(x),
+        Node::Strong(x) => visitor.visit_strong_mut(x),
+        Node::Strikethrough(x) => visitor.visit_strikethrough_mut(x),
+        Node::Link(x) => visitor.visit_link_mut(x),
+        Node::Image(x) => visitor.visit_image_mut(x),
+        Node::Autolink(x) => visitor.visit_autolink_mut(x),
+        Node::FootnoteReference(x) => visitor.visit_footnote_reference_mut(x),
+        Node::InlineHtml(x) => visitor.visit_inline_html_mut(x),
+        Node::SoftBreak(x) => visitor.visit_soft_break_mut(x),
+        Node::HardBreak(x) => visitor.visit_hard_break_mut(x),
+        Node::Text(x) => visitor.visit_text_mut(x),
+    }
+}
+
+/// Visit each of a list of nodes in order, mutably.
+pub fn walk_children_mut<V: VisitorMut + ?Sized>(visitor: &mut V, children: &mut [Node]) {
+    for child in children {
+        visitor.visit_node_mut(child);
+    }
+}
+
+/// Fold over a syntax tree.
+///
+/// Each hook takes ownership of a node and returns the nodes that replace it:
+/// none to remove it, one to keep or replace it, or several to splice them
+/// into the parent in its place.
+pub trait Fold {
+    /// Fold a node, dispatching to the hook for its type.
+    fn fold_node(&mut self, node: Node) -> Vec<Node> {
+        fold_node(self, node)
+    }
+
+    fn fold_root(&mut self, mut root: Root) -> Vec<Node> {
+        root.children = fold_children(self, root.children);
+        vec![Node::Root(root)]
+    }
+
+    fn fold_block_quote(&mut self, mut block_quote: BlockQuote) -> Vec<Node> {
+        block_quote.children = fold_children(self, block_quote.children);
+        vec![Node::BlockQuote(block_quote)]
+    }
+
+    fn fold_list(&mut self, mut list: List) -> Vec<Node> {
+        list.children = fold_children(self, list.children);
+        vec![Node::List(list)]
+    }
+
+    fn fold_list_item(&mut self, mut list_item: ListItem) -> Vec<Node> {
+        list_item.children = fold_children(self, list_item.children);
+        vec![Node::ListItem(list_item)]
+    }
+
+    fn fold_footnote_definition(
+        &mut self,
+        mut footnote_definition: FootnoteDefinition,
+    ) -> Vec<Node> {
+        footnote_definition.children = fold_children(self, footnote_definition.children);
+        vec![Node::FootnoteDefinition(footnote_definition)]
+    }
+
+    fn fold_front_matter(&mut self, front_matter: FrontMatter) -> Vec<Node> {
+        vec![Node::FrontMatter(front_matter)]
+    }
+
+    fn fold_thematic_break(&mut self, thematic_break: ThematicBreak) -> Vec<Node> {
+        vec![Node::ThematicBreak(thematic_break)]
+    }
+
+    fn fold_heading(&mut self, mut heading: Heading) -> Vec<Node> {
+        heading.children = fold_children(self, heading.children);
+        vec![Node::Heading(heading)]
+    }
+
+    fn fold_paragraph(&mut self, mut paragraph: Paragraph) -> Vec<Node> {
+        paragraph.children = fold_children(self, paragraph.children);
+        vec![Node::Paragraph(paragraph)]
+    }
+
+    fn fold_code(&mut self, code: Code) -> Vec<Node> {
+        vec![Node::Code(code)]
+    }
+
+    fn fold_html(&mut self, html: Html) -> Vec<Node> {
+        vec![Node::Html(html)]
+    }
+
+    fn fold_table(&mut self, mut table: Table) -> Vec<Node> {
+        table.children = fold_children(self, table.children);
+        vec![Node::Table(table)]
+    }
+
+    fn fold_table_row(&mut self, mut table_row: TableRow) -> Vec<Node> {
+        table_row.children = fold_children(self, table_row.children);
+        vec![Node::TableRow(table_row)]
+    }
+
+    fn fold_table_cell(&mut self, mut table_cell: TableCell) -> Vec<Node> {
+        table_cell.children = fold_children(self, table_cell.children);
+        vec![Node::TableCell(table_cell)]
+    }
+
+    fn fold_code_span(&mut self, code_span: CodeSpan) -> Vec<Node> {
+        vec![Node::CodeSpan(code_span)]
+    }
+
+    fn fold_emphasis(&mut self, mut emphasis: Emphasis) -> Vec<Node> {
+        emphasis.children = fold_children(self, emphasis.children);
+        vec![Node::Emphasis(emphasis)]
+    }
+
+    fn fold_strong(&mut self, mut strong: Strong) -> Vec<Node> {
+        strong.children = fold_children(self, strong.children);
+        vec![Node::Strong(strong)]
+    }
+
+    fn fold_strikethrough(&mut self, mut strikethrough: Strikethrough) -> Vec<Node> {
+        strikethrough.children = fold_children(self, strikethrough.children);
+        vec![Node::Strikethrough(strikethrough)]
+    }
+
+    fn fold_link(&mut self, mut link: Link) -> Vec<Node> {
+        link.children = fold_children(self, link.children);
+        vec![Node::Link(link)]
+    }
+
+    fn fold_image(&mut self, mut image: Image) -> Vec<Node> {
+        image.children = fold_children(self, image.children);
+        vec![Node::Image(image)]
+    }
+
+    fn fold_autolink(&mut self, autolink: Autolink) -> Vec<Node> {
+        vec![Node::Autolink(autolink)]
+    }
+
+    fn fold_footnote_reference(&mut self, footnote_reference: FootnoteReference) -> Vec<Node> {
+        vec![Node::FootnoteReference(footnote_reference)]
+    }
+
+    fn fold_inline_html(&mut self, inline_html: InlineHtml) -> Vec<Node> {
+        vec![Node::InlineHtml(inline_html)]
+    }
+
+    fn fold_soft_break(&mut self, soft_break: LineBreak) -> Vec<Node> {
+        vec![Node::SoftBreak(soft_break)]
+    }
+
+    fn fold_hard_break(&mut self, hard_break: LineBreak) -> Vec<Node> {
+        vec![Node::HardBreak(hard_break)]
+    }
+
+    fn fold_text(&mut self, text: Text) -> Vec<Node> {
+        vec![Node::Text(text)]
+    }
+}
+
+/// Call the hook of a fold for the type of a node.
+pub fn fold_node<F: Fold + ?Sized>(folder: &mut F, node: Node) -> Vec<Node> {
+    match node {
+        Node::Root(x) => folder.fold_root(x),
+        Node::BlockQuote(x) => folder.fold_block_quote(x),
+        Node::List(x) => folder.fold_list(x),
+        Node::ListItem(x) => folder.fold_list_item(x),
+        Node::FootnoteDefinition(x) => folder.fold_footnote_definition(x),
+        Node::FrontMatter(x) => folder.fold_front_matter(x),
+        Node::ThematicBreak(x) => folder.fold_thematic_break(x),
+        Node::Heading(x) => folder.fold_heading(x),
+        Node::Paragraph(x) => folder.fold_paragraph(x),
+        Node::Code(x) => folder.fold_code(x),
+        Node::Html(x) => folder.fold_html(x),
+        Node::Table(x) => folder.fold_table(x),
+        Node::TableRow(x) => folder.fold_table_row(x),
+        Node::TableCell(x) => folder.fold_table_cell(x),
+        Node::CodeSpan(x) => folder.fold_code_span(x),
+        Node::Emphasis(x) => folder.fold_emphasis(x),
+        Node::Strong(x) => folder.fold_strong(x),
+        Node::Strikethrough(x) => folder.fold_strikethrough(x),
+        Node::Link(x) => folder.fold_link(x),
+        Node::Image(x) => folder.fold_image(x),
+        Node::Autolink(x) => folder.fold_autolink(x),
+        Node::FootnoteReference(x) => folder.fold_footnote_reference(x),
+        Node::InlineHtml(x) => folder.fold_inline_html(x),
+        Node::SoftBreak(x) => folder.fold_soft_break(x),
+        Node::HardBreak(x) => folder.fold_hard_break(x),
+        Node::Text(x) => folder.fold_text(x),
+    }
+}
+
+/// Fold each of a list of nodes in order, collecting the nodes that replace
+/// them.
+pub fn fold_children<F: Fold + ?Sized>(folder: &mut F, children: Vec<Node>) -> Vec<Node> {
+    children
+        .into_iter()
+        .flat_map(|child| folder.fold_node(child))
+        .collect()
+}
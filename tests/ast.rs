@@ -0,0 +1,194 @@
+use markdown::{
+    ast::{Attributes, CodeBlockType, FrontMatterKind, Node, Point, Position, Text},
+    Options,
+};
+
+#[test]
+fn parse_root() {
+    let root = markdown::parse("> foo\n\nbar");
+    let Node::Root(root) = &root else {
+        panic!("expected root");
+    };
+    assert_eq!(root.children.len(), 2);
+    let Node::BlockQuote(quote) = &root.children[0] else {
+        panic!("expected block quote");
+    };
+    assert!(matches!(quote.children[0], Node::Paragraph(_)));
+    assert!(matches!(root.children[1], Node::Paragraph(_)));
+}
+
+#[test]
+fn transform_and_render() {
+    let mut root = markdown::parse("foo");
+    let Node::Root(x) = &mut root else {
+        panic!("expected root");
+    };
+    let Node::Paragraph(para) = &mut x.children[0] else {
+        panic!("expected paragraph");
+    };
+    para.children = vec![Node::Text(Text::new("bar".into()))];
+    assert_eq!(root.to_string(), "<p>bar</p>\n");
+}
+
+#[test]
+fn block_positions() {
+    let root = markdown::parse("# foo\n\n> bar\n> baz\n");
+    assert_eq!(
+        root.position(),
+        Position::new(Point::new(1, 1, 0), Point::new(4, 6, 18))
+    );
+    let Node::Root(root) = &root else {
+        panic!("expected root");
+    };
+    assert_eq!(
+        root.children[0].position(),
+        Position::new(Point::new(1, 1, 0), Point::new(1, 6, 5))
+    );
+    let Node::BlockQuote(quote) = &root.children[1] else {
+        panic!("expected block quote");
+    };
+    assert_eq!(
+        quote.position,
+        Position::new(Point::new(3, 1, 7), Point::new(4, 6, 18))
+    );
+    assert_eq!(
+        quote.children[0].position(),
+        Position::new(Point::new(3, 3, 9), Point::new(4, 6, 18))
+    );
+}
+
+#[test]
+fn inline_positions() {
+    let root = markdown::parse("> foo *bar*\n> [baz](/url)");
+    let Node::Root(root) = &root else {
+        panic!("expected root");
+    };
+    let Node::BlockQuote(quote) = &root.children[0] else {
+        panic!("expected block quote");
+    };
+    let Node::Paragraph(para) = &quote.children[0] else {
+        panic!("expected paragraph");
+    };
+    let positions: Vec<_> = para.children.iter().map(|c| c.position()).collect();
+    assert_eq!(
+        positions,
+        vec![
+            Position::new(Point::new(1, 3, 2), Point::new(1, 7, 6)),
+            Position::new(Point::new(1, 7, 6), Point::new(1, 12, 11)),
+            Position::new(Point::new(1, 12, 11), Point::new(2, 3, 14)),
+            Position::new(Point::new(2, 3, 14), Point::new(2, 14, 25)),
+        ]
+    );
+}
+
+#[test]
+fn source_positions_in_html() {
+    let options = Options::new().source_positions(true);
+    let html = markdown::to_html_with_options("- foo\n\n  bar\n\n***", &options);
+    assert_eq!(
+        html,
+        concat!(
+            "<ul data-sourcepos=\"1:1-3:5\">\n",
+            "<li data-sourcepos=\"1:1-3:5\">\n",
+            "<p data-sourcepos=\"1:3-1:5\">foo</p>\n",
+            "<p data-sourcepos=\"3:3-3:5\">bar</p>\n",
+            "</li>\n",
+            "</ul>\n",
+            "<hr data-sourcepos=\"5:1-5:3\" />\n",
+        )
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_render_options() {
+    let html = markdown::to_html_with_soft_break("foo\nbar", markdown::SoftBreak::Space);
+    assert_eq!(html, "<p>foo bar</p>\n");
+    let options = markdown::RenderOptions {
+        source_positions: true,
+        ..Default::default()
+    };
+    let html = markdown::to_html_with_render_options("foo", options);
+    assert_eq!(html, "<p data-sourcepos=\"1:1-1:3\">foo</p>\n");
+}
+
+#[test]
+fn front_matter() {
+    let options = Options::new().front_matter(true);
+    let root = markdown::parse_with_options("---\ntitle: foo\n---\nbar\n", &options);
+    let Node::Root(root) = &root else {
+        panic!("expected root");
+    };
+    let front_matter = root.front_matter().expect("expected front matter");
+    assert_eq!(front_matter.kind, FrontMatterKind::Yaml);
+    assert_eq!(front_matter.text, "title: foo\n");
+    assert_eq!(
+        front_matter.position,
+        Position::new(Point::new(1, 1, 0), Point::new(3, 4, 18))
+    );
+    assert!(matches!(root.children[1], Node::Paragraph(_)));
+}
+
+#[test]
+fn attributes() {
+    let options = Options::new().attributes(true);
+    let root =
+        markdown::parse_with_options("foo {#bar .baz}\n---\n\n```rust {x=1}\n```\n", &options);
+    let Node::Root(root) = &root else {
+        panic!("expected root");
+    };
+    let Node::Heading(heading) = &root.children[0] else {
+        panic!("expected heading");
+    };
+    assert_eq!(heading.id.as_deref(), Some("bar"));
+    assert_eq!(heading.attributes.classes, vec!["baz".to_string()]);
+    assert_eq!(
+        heading.children[0].position(),
+        Position::new(Point::new(1, 1, 0), Point::new(1, 4, 3))
+    );
+    let Node::Code(code) = &root.children[1] else {
+        panic!("expected code");
+    };
+    assert_eq!(code.info.as_deref(), Some("rust"));
+    assert_eq!(
+        code.attributes,
+        Attributes {
+            id: None,
+            classes: vec![],
+            attrs: vec![("x".into(), "1".into())],
+        }
+    );
+}
+
+#[test]
+fn code_block_types() {
+    let root = markdown::parse("    foo\n\n  ````rust\n  bar\n  ````\n\n~~~\nbaz\n~~~\n");
+    let Node::Root(root) = &root else {
+        panic!("expected root");
+    };
+    let types: Vec<_> = root
+        .children
+        .iter()
+        .map(|child| match child {
+            Node::Code(code) => (code.block_type, code.indent, code.text.as_str()),
+            _ => panic!("expected code"),
+        })
+        .collect();
+    assert_eq!(
+        types,
+        vec![
+            (CodeBlockType::Indented, 4, "foo\n"),
+            (CodeBlockType::Fenced('`', 4), 2, "bar\n"),
+            (CodeBlockType::Fenced('~', 3), 0, "baz\n"),
+        ]
+    );
+}
+
+#[test]
+fn compare_nodes() {
+    let root = markdown::parse("*foo*");
+    assert_eq!(root, markdown::parse("*foo*"));
+    assert_ne!(root, markdown::parse("*bar*"));
+    let text = Node::Text(Text::new("foo".into()));
+    assert!(format!("{text:?}").starts_with("Text(Text { text: \"foo\""));
+}
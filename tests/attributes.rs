@@ -0,0 +1,175 @@
+mod macros;
+
+mdtest!(
+    heading_attributes,
+    markdown::Options::new().attributes(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    ## Title {#custom-id .hidden}
+
+    # Foo # {.a .b}
+
+    # Bar {#first .c #last}
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h2 id="custom-id" class="hidden">Title</h2>
+    <h1 class="a b">Foo</h1>
+    <h1 id="last" class="c">Bar</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    setext_heading_attributes,
+    markdown::Options::new().attributes(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    foo
+    bar {#foobar data-level=1}
+    ===
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 id="foobar" data-level="1">foo
+    bar</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    code_attributes,
+    markdown::Options::new().attributes(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    ```rust {.numberLines startFrom=10}
+    fn main() {}
+    ```
+
+    ~~~ {#example title="An example"}
+    x
+    ~~~
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <pre><code class="language-rust numberLines" startFrom="10">fn main() {}
+    </code></pre>
+    <pre><code id="example" title="An example">x
+    </code></pre>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    not_attributes,
+    markdown::Options::new().attributes(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo {#bar} baz
+
+    # foo {.bar\}
+
+    # foo {#bar
+
+    # foo \{#bar}
+
+    foo {
+    .bar}
+    ---
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1>foo {#bar} baz</h1>
+    <h1>foo {.bar}</h1>
+    <h1>foo {#bar</h1>
+    <h1>foo {#bar}</h1>
+    <h2>foo {
+    .bar}</h2>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    escaped_attributes,
+    markdown::Options::new().attributes(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo {.a"b&c}
+
+    ```{title="<b>"}
+    ```
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 class="a&quot;b&amp;c">foo</h1>
+    <pre><code title="&lt;b&gt;"></code></pre>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    event_handler_attributes,
+    markdown::Options::new().attributes(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo {onclick=alert(1) title=bar}
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 title="bar">foo</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    unsafe_event_handler_attributes,
+    markdown::Options::new().attributes(true).unsafe_html(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo {onclick=alert(1)}
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 onclick="alert(1)">foo</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    attributes_with_heading_ids,
+    markdown::Options::new().attributes(true).heading_ids(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo
+
+    # bar {#foo-1}
+
+    # foo
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 id="foo">foo</h1>
+    <h1 id="foo-1">bar</h1>
+    <h1 id="foo-2">foo</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    attributes_disabled,
+    markdown::Options::new(),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo {#bar}
+
+    ```rust {.baz}
+    ```
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1>foo {#bar}</h1>
+    <pre><code class="language-rust"></code></pre>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    named_id_and_class_attributes,
+    markdown::Options::new().attributes(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo {#x id=y}
+
+    # bar {class=x .y}
+
+    ```{.a class="b c" data-x=1}
+    ```
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 id="y">foo</h1>
+    <h1 class="x y">bar</h1>
+    <pre><code class="a b c" data-x="1"></code></pre>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
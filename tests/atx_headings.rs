@@ -62,9 +62,8 @@ mdtest!(
 );
 
 // 66
-mdtest_ignore!(
+mdtest!(
     contents_as_inline,
-    "inline not supported",
     r"
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     # foo *bar* \*baz\*
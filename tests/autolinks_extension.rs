@@ -0,0 +1,283 @@
+mod macros;
+
+mdtest!(
+    www_autolink,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    www.commonmark.org
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="http://www.commonmark.org">www.commonmark.org</a></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    www_autolink_with_path,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    Visit www.commonmark.org/help for more information.
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>Visit <a href="http://www.commonmark.org/help">www.commonmark.org/help</a> for more information.</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    trailing_punctuation,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    Visit www.commonmark.org.
+
+    Visit www.commonmark.org/a.b.
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>Visit <a href="http://www.commonmark.org">www.commonmark.org</a>.</p>
+    <p>Visit <a href="http://www.commonmark.org/a.b">www.commonmark.org/a.b</a>.</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    balanced_parentheses,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    www.google.com/search?q=Markup+(business)
+
+    www.google.com/search?q=Markup+(business)))
+
+    (www.google.com/search?q=Markup+(business))
+
+    (www.google.com/search?q=Markup+(business)
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="http://www.google.com/search?q=Markup+(business)">www.google.com/search?q=Markup+(business)</a></p>
+    <p><a href="http://www.google.com/search?q=Markup+(business)">www.google.com/search?q=Markup+(business)</a>))</p>
+    <p>(<a href="http://www.google.com/search?q=Markup+(business)">www.google.com/search?q=Markup+(business)</a>)</p>
+    <p>(<a href="http://www.google.com/search?q=Markup+(business)">www.google.com/search?q=Markup+(business)</a></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    inner_parentheses,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    www.google.com/search?q=(business))+ok
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="http://www.google.com/search?q=(business))+ok">www.google.com/search?q=(business))+ok</a></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    entity_like_suffix,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    www.google.com/search?q=commonmark&hl=en
+
+    www.google.com/search?q=commonmark&hl;
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="http://www.google.com/search?q=commonmark&amp;hl=en">www.google.com/search?q=commonmark&amp;hl=en</a></p>
+    <p><a href="http://www.google.com/search?q=commonmark">www.google.com/search?q=commonmark</a>&amp;hl;</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    less_than_ends_autolink,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    www.commonmark.org/he<lp
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="http://www.commonmark.org/he">www.commonmark.org/he</a>&lt;lp</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    url_autolink,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    http://commonmark.org
+
+    (Visit https://encrypted.google.com/search?q=Markup+(business))
+
+    Anonymous FTP is available at ftp://foo.bar.baz.
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="http://commonmark.org">http://commonmark.org</a></p>
+    <p>(Visit <a href="https://encrypted.google.com/search?q=Markup+(business)">https://encrypted.google.com/search?q=Markup+(business)</a>)</p>
+    <p>Anonymous FTP is available at <a href="ftp://foo.bar.baz">ftp://foo.bar.baz</a>.</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    email_autolink,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    foo@bar.baz
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="mailto:foo@bar.baz">foo@bar.baz</a></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    email_autolink_plus,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    hello@mail+xyz.example isn't valid, but hello+xyz@mail.example is.
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>hello@mail+xyz.example isn't valid, but <a href="mailto:hello+xyz@mail.example">hello+xyz@mail.example</a> is.</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    email_autolink_end,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    a.b-c_d@a.b
+
+    a.b-c_d@a.b.
+
+    a.b-c_d@a.b-
+
+    a.b-c_d@a.b_
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="mailto:a.b-c_d@a.b">a.b-c_d@a.b</a></p>
+    <p><a href="mailto:a.b-c_d@a.b">a.b-c_d@a.b</a>.</p>
+    <p>a.b-c_d@a.b-</p>
+    <p>a.b-c_d@a.b_</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    protocol_autolink,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    mailto:foo@bar.baz
+
+    mailto:a.b-c_d@a.b
+
+    mailto:a.b-c_d@a.b.
+
+    mailto:a.b-c_d@a.b/
+
+    mailto:a.b-c_d@a.b-
+
+    mailto:a.b-c_d@a.b_
+
+    xmpp:foo@bar.baz
+
+    xmpp:foo@bar.baz.
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="mailto:foo@bar.baz">mailto:foo@bar.baz</a></p>
+    <p><a href="mailto:a.b-c_d@a.b">mailto:a.b-c_d@a.b</a></p>
+    <p><a href="mailto:a.b-c_d@a.b">mailto:a.b-c_d@a.b</a>.</p>
+    <p><a href="mailto:a.b-c_d@a.b">mailto:a.b-c_d@a.b</a>/</p>
+    <p>mailto:a.b-c_d@a.b-</p>
+    <p>mailto:a.b-c_d@a.b_</p>
+    <p><a href="xmpp:foo@bar.baz">xmpp:foo@bar.baz</a></p>
+    <p><a href="xmpp:foo@bar.baz">xmpp:foo@bar.baz</a>.</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    xmpp_resource,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    xmpp:foo@bar.baz/txt
+
+    xmpp:foo@bar.baz/txt@bin
+
+    xmpp:foo@bar.baz/txt@bin.com
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="xmpp:foo@bar.baz/txt">xmpp:foo@bar.baz/txt</a></p>
+    <p><a href="xmpp:foo@bar.baz/txt@bin">xmpp:foo@bar.baz/txt@bin</a></p>
+    <p><a href="xmpp:foo@bar.baz/txt@bin.com">xmpp:foo@bar.baz/txt@bin.com</a></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    autolink_in_emphasis,
+    markdown::Options::new().autolink(true).strikethrough(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    *www.commonmark.org* and ~~http://example.com~~
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><em><a href="http://www.commonmark.org">www.commonmark.org</a></em> and <del><a href="http://example.com">http://example.com</a></del></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    autolink_not_in_link,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    [www.commonmark.org](http://example.com) and <http://foo.bar.baz>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><a href="http://example.com">www.commonmark.org</a> and <a href="http://foo.bar.baz">http://foo.bar.baz</a></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    autolink_after_word,
+    markdown::Options::new().autolink(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    xwww.commonmark.org and domains without a period: http://localhost
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>xwww.commonmark.org and domains without a period: http://localhost</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    email_autolink_non_ascii,
+    markdown::Options::new().autolink(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    é@x
+
+    ß@é
+
+    a ü@b.c
+
+    üfoo@bar.baz
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>é@x</p>
+    <p>ß@é</p>
+    <p>a ü@b.c</p>
+    <p>ü<a href="mailto:foo@bar.baz">foo@bar.baz</a></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    autolink_disabled,
+    markdown::Options::new(),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    www.commonmark.org
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>www.commonmark.org</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
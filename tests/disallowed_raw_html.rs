@@ -0,0 +1,39 @@
+mod macros;
+
+// disallowed raw html
+mdtest!(
+    tagfilter,
+    markdown::Options::new().unsafe_html(true).tagfilter(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <strong> <title> <style> <em>
+
+    <blockquote>
+      <xmp> is disallowed.  <XMP> is also disallowed.
+    </blockquote>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p><strong> &lt;title> &lt;style> <em></p>
+    <blockquote>
+      &lt;xmp> is disallowed.  &lt;XMP> is also disallowed.
+    </blockquote>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    tagfilter_html_types,
+    markdown::Options::new().unsafe_html(true).tagfilter(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <script>alert(1)</script>
+
+    <iframe src=\"x\"></iframe>
+
+    <titles> <textarea/>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    &lt;script>alert(1)&lt;/script>
+    &lt;iframe src=\"x\">&lt;/iframe>
+    <p><titles> &lt;textarea/></p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
@@ -0,0 +1,164 @@
+use markdown::{
+    ast::{Alignment, Attributes, Point, Position},
+    event::{Event, Events, Tag},
+    html, Options,
+};
+
+#[test]
+fn blocks() {
+    let root = markdown::parse("# foo\n\n1. bar\n\n```rust\nbaz\n```\n\n***");
+    let events: Vec<Event> = Events::new(&root).collect();
+    assert_eq!(
+        events,
+        vec![
+            Event::Start(Tag::Heading {
+                level: 1,
+                id: None,
+                attributes: &Attributes::new(),
+            }),
+            Event::Text("foo"),
+            Event::End(Tag::Heading {
+                level: 1,
+                id: None,
+                attributes: &Attributes::new(),
+            }),
+            Event::Start(Tag::List(Some(1))),
+            Event::Start(Tag::Item),
+            Event::Text("bar"),
+            Event::End(Tag::Item),
+            Event::End(Tag::List(Some(1))),
+            Event::Start(Tag::CodeBlock(Some("rust"), &Attributes::new())),
+            Event::Text("baz\n"),
+            Event::End(Tag::CodeBlock(Some("rust"), &Attributes::new())),
+            Event::Rule,
+        ]
+    );
+}
+
+#[test]
+fn inlines() {
+    let root = markdown::parse("[`foo`](/url \"title\") ![bar](/img)\n<http://a.b> <b>");
+    let events: Vec<Event> = Events::new(&root).collect();
+    let link = Tag::Link {
+        destination: "/url",
+        title: Some("title"),
+    };
+    let image = Tag::Image {
+        destination: "/img",
+        title: None,
+    };
+    assert_eq!(
+        events,
+        vec![
+            Event::Start(Tag::Paragraph),
+            Event::Start(link.clone()),
+            Event::Code("foo"),
+            Event::End(link),
+            Event::Text(" "),
+            Event::Start(image.clone()),
+            Event::Text("bar"),
+            Event::End(image),
+            Event::SoftBreak,
+            Event::Start(Tag::Autolink("http://a.b")),
+            Event::Text("http://a.b"),
+            Event::End(Tag::Autolink("http://a.b")),
+            Event::Text(" "),
+            Event::InlineHtml("<b>"),
+            Event::End(Tag::Paragraph),
+        ]
+    );
+}
+
+#[test]
+fn table() {
+    let options = Options::new().tables(true);
+    let root = markdown::parse_with_options("a | b\n:- | -\nc", &options);
+    let events: Vec<Event> = Events::new(&root).collect();
+    let table = Tag::Table(&[Alignment::Left, Alignment::None]);
+    assert_eq!(
+        events,
+        vec![
+            Event::Start(table.clone()),
+            Event::Start(Tag::TableHead),
+            Event::Start(Tag::TableCell),
+            Event::Text("a"),
+            Event::End(Tag::TableCell),
+            Event::Start(Tag::TableCell),
+            Event::Text("b"),
+            Event::End(Tag::TableCell),
+            Event::End(Tag::TableHead),
+            Event::Start(Tag::TableRow),
+            Event::Start(Tag::TableCell),
+            Event::Text("c"),
+            Event::End(Tag::TableCell),
+            Event::Start(Tag::TableCell),
+            Event::End(Tag::TableCell),
+            Event::End(Tag::TableRow),
+            Event::End(table),
+        ]
+    );
+}
+
+#[test]
+fn task_list() {
+    let options = Options::new().tasklist(true);
+    let root = markdown::parse_with_options("- [x] foo", &options);
+    let events: Vec<Event> = Events::new(&root).collect();
+    assert_eq!(
+        events,
+        vec![
+            Event::Start(Tag::List(None)),
+            Event::Start(Tag::Item),
+            Event::TaskListMarker(true),
+            Event::Text("foo"),
+            Event::End(Tag::Item),
+            Event::End(Tag::List(None)),
+        ]
+    );
+}
+
+#[test]
+fn footnotes() {
+    let options = Options::new().footnotes(true);
+    let root = markdown::parse_with_options("foo[^1]\n\n[^1]: bar", &options);
+    let events: Vec<Event> = Events::new(&root).collect();
+    assert_eq!(
+        events,
+        vec![
+            Event::Start(Tag::Paragraph),
+            Event::Text("foo"),
+            Event::FootnoteReference("1"),
+            Event::End(Tag::Paragraph),
+            Event::Start(Tag::FootnoteDefinition("1")),
+            Event::Start(Tag::Paragraph),
+            Event::Text("bar"),
+            Event::End(Tag::Paragraph),
+            Event::End(Tag::FootnoteDefinition("1")),
+        ]
+    );
+}
+
+#[test]
+fn positions() {
+    let root = markdown::parse("foo\n*bar*");
+    let positions: Vec<Position> = Events::new(&root)
+        .with_positions()
+        .map(|(_, position)| position)
+        .collect();
+    let paragraph = Position::new(Point::new(1, 1, 0), Point::new(2, 6, 9));
+    let emphasis = Position::new(Point::new(2, 1, 4), Point::new(2, 6, 9));
+    assert_eq!(positions[0], paragraph);
+    assert_eq!(positions[3], emphasis);
+    assert_eq!(positions[6], paragraph);
+}
+
+#[test]
+fn render_filtered_events() {
+    let root = markdown::parse("foo *bar* <b>baz</b>");
+    let events = Events::new(&root)
+        .with_positions()
+        .filter(|(event, _)| !matches!(event, Event::InlineHtml(_)));
+    let mut output = String::new();
+    html::write_html(&mut output, events, &Default::default()).unwrap();
+    assert_eq!(output, "<p>foo <em>bar</em> baz</p>\n");
+}
@@ -35,9 +35,8 @@ mdtest!(
 );
 
 // 121
-mdtest_ignore!(
+mdtest!(
     insufficient_delims,
-    "inline code not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     ``
@@ -315,9 +314,8 @@ mdtest!(
 );
 
 // 138
-mdtest_ignore!(
+mdtest!(
     no_internal_spaces,
-    "inline code not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     ``` ```
@@ -436,9 +434,8 @@ mdtest!(
 );
 
 // 145
-mdtest_ignore!(
+mdtest!(
     info_string_backtick_backtick,
-    "inline code not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     ``` aa ```
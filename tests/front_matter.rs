@@ -0,0 +1,98 @@
+mod macros;
+
+mdtest!(
+    yaml_front_matter,
+    markdown::Options::new().front_matter(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    ---
+    title: Hello
+    tags: [a, b]
+    ---
+    # Heading
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1>Heading</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    toml_front_matter,
+    markdown::Options::new().front_matter(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    +++
+    title = "Hello"
+    +++
+
+    text
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>text</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    yaml_front_matter_closed_with_dots,
+    markdown::Options::new().front_matter(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    ---
+    title: Hello
+    ...
+    text
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>text</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    unclosed_front_matter,
+    markdown::Options::new().front_matter(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    ---
+    title: Hello
+
+    text
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <hr />
+    <p>title: Hello</p>
+    <p>text</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    front_matter_not_at_start,
+    markdown::Options::new().front_matter(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    text
+
+    ---
+    title: Hello
+    ---
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>text</p>
+    <hr />
+    <h2>title: Hello</h2>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    front_matter_disabled,
+    markdown::Options::new(),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    ---
+    title: Hello
+    ---
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <hr />
+    <h2>title: Hello</h2>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
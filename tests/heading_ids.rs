@@ -0,0 +1,98 @@
+mod macros;
+
+mdtest!(
+    heading_ids,
+    markdown::Options::new().heading_ids(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # Hello World
+
+    ## Foo: *bar* & `baz`!
+
+    # Über café
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 id="hello-world">Hello World</h1>
+    <h2 id="foo-bar--baz">Foo: <em>bar</em> &amp; <code>baz</code>!</h2>
+    <h1 id="über-café">Über café</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    multiline_heading_id,
+    markdown::Options::new().heading_ids(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    foo
+    bar
+    ===
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 id="foobar">foo
+    bar</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    duplicate_heading_ids,
+    markdown::Options::new().heading_ids(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo
+    # foo
+    # foo-1
+    # foo
+    > # foo
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 id="foo">foo</h1>
+    <h1 id="foo-1">foo</h1>
+    <h1 id="foo-1-1">foo-1</h1>
+    <h1 id="foo-2">foo</h1>
+    <blockquote>
+    <h1 id="foo-3">foo</h1>
+    </blockquote>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    heading_anchors,
+    markdown::Options::new()
+        .heading_ids(true)
+        .heading_anchors(true),
+    r##"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo *bar* [link](/u)
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 id="foo-bar-link"><a class="anchor" href="#foo-bar-link" aria-hidden="true"></a>foo <em>bar</em> <a href="/u">link</a></h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "##
+);
+
+mdtest!(
+    custom_slugger,
+    markdown::Options::new()
+        .heading_ids(true)
+        .slugger(markdown::Slugger::new(|text| text.replace(' ', "_"))),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # Foo Bar
+    # Foo Bar
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1 id="Foo_Bar">Foo Bar</h1>
+    <h1 id="Foo_Bar-1">Foo Bar</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    heading_ids_disabled,
+    markdown::Options::new(),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    # foo
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <h1>foo</h1>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
@@ -1,9 +1,8 @@
 mod macros;
 
 // 148
-mdtest_ignore!(
+mdtest!(
     end_any_blank,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <table><tr><td>
@@ -82,9 +81,8 @@ mdtest!(
 );
 
 // 152
-mdtest_ignore!(
+mdtest!(
     html_para_html,
-    "emphasis not supported",
     r#"
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <DIV CLASS="foo">
@@ -133,9 +131,8 @@ mdtest!(
 );
 
 // 155
-mdtest_ignore!(
+mdtest!(
     unclosed_tag,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <div>
@@ -317,9 +314,8 @@ mdtest!(
 );
 
 // 167
-mdtest_ignore!(
+mdtest!(
     del_2,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <del>
@@ -336,9 +332,8 @@ mdtest_ignore!(
 );
 
 // 168
-mdtest_ignore!(
+mdtest!(
     del_3,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <del>*foo*</del>
@@ -498,9 +493,8 @@ mdtest!(
 );
 
 // 176
-mdtest_ignore!(
+mdtest!(
     end_same_line,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <style>p{color:red;}</style>
@@ -513,9 +507,8 @@ mdtest_ignore!(
 );
 
 // 177
-mdtest_ignore!(
+mdtest!(
     end_same_line_2,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <!-- foo -->*bar*
@@ -718,9 +711,8 @@ mdtest!(
 );
 
 // 188
-mdtest_ignore!(
+mdtest!(
     interrupt_7_2,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <div>
@@ -376,9 +376,8 @@ mdtest!(
 );
 
 // 317
-mdtest_ignore!(
+mdtest!(
     loose_4,
-    "reference def not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     - a
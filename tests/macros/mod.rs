@@ -1,6 +1,8 @@
 /// Generates a test that compares the parsed Markdown input against the
 /// expected HTML output. Use the following separator to frame the input and
-/// expected output: `~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~`.
+/// expected output: `~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~`. Raw HTML is
+/// rendered as-is, as the spec expects, unless options are given before the
+/// test.
 ///
 /// # Examples
 ///
@@ -22,15 +24,18 @@
 #[macro_export]
 macro_rules! mdtest {
     ($name:ident, $test:expr) => {
+        mdtest!($name, markdown::Options::new().unsafe_html(true), $test);
+    };
+    ($name:ident, $options:expr, $test:expr) => {
         #[test]
         fn $name() {
             use indoc::indoc;
-            use markdown::to_html;
+            use markdown::to_html_with_options;
 
             let separator = "~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~";
             let components: Vec<&str> = indoc!($test).split(separator).collect();
             let markdown = &components[1][1..]; // skip leading newline
-            let html = to_html(markdown);
+            let html = to_html_with_options(markdown, &$options);
             let expected = &components[2][1..]; // skip leading newline
             if html != expected {
                 panic!(
@@ -71,12 +76,12 @@ macro_rules! mdtest_ignore {
         #[ignore = $reason]
         fn $name() {
             use indoc::indoc;
-            use markdown::to_html;
+            use markdown::{to_html_with_options, Options};
 
             let separator = "~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~";
             let components: Vec<&str> = indoc!($test).split(separator).collect();
             let markdown = &components[1][1..]; // skip leading newline
-            let html = to_html(markdown);
+            let html = to_html_with_options(markdown, &Options::new().unsafe_html(true));
             let expected = &components[2][1..]; // skip leading newline
             assert_eq!(html, expected);
         }
@@ -110,9 +110,8 @@ mdtest!(
 );
 
 // 226
-mdtest_ignore!(
+mdtest!(
     trailing_spaces,
-    "hard line break not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     aaa     
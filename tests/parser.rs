@@ -0,0 +1,87 @@
+use markdown::{ast::Node, Options, Parser};
+
+/// Render a tree with source positions, so that positions are compared too.
+fn render(node: &Node) -> String {
+    let options = Options::new().source_positions(true);
+    let mut html = String::new();
+    node.render(&mut html, &options).unwrap();
+    html
+}
+
+#[test]
+fn chunks_match_whole_text() {
+    let text =
+        "# foo\r\n\n> bar\n> *baz*\n\n```rust\nfn main() {}\n\n```\n\n- [a]\n- b\n\n[a]: /url\n";
+    let expected = render(&markdown::parse(text));
+    for i in 0..=text.len() {
+        let mut parser = Parser::new();
+        parser.feed(&text[..i]);
+        parser.feed(&text[i..]);
+        assert_eq!(render(&parser.finish()), expected, "split at {i}");
+    }
+}
+
+#[test]
+fn front_matter_across_feeds() {
+    let options = Options::new().front_matter(true);
+    for text in ["---\ntitle: foo\n---\nbar\n", "---\ntitle: foo\n\nbar"] {
+        let expected = render(&markdown::parse_with_options(text, &options));
+        for i in 0..=text.len() {
+            let mut parser = Parser::with_options(options.clone());
+            parser.feed(&text[..i]);
+            parser.feed(&text[i..]);
+            assert_eq!(render(&parser.finish()), expected, "split at {i}");
+        }
+    }
+}
+
+#[test]
+fn fenced_code_across_feeds() {
+    let mut parser = Parser::new();
+    parser.feed("``");
+    parser.feed("`\nfoo\n");
+    parser.feed("\nbar\n`");
+    parser.feed("``");
+    assert_eq!(
+        parser.finish().to_string(),
+        "<pre><code>foo\n\nbar\n</code></pre>\n"
+    );
+}
+
+#[test]
+fn closed_blocks() {
+    let mut parser = Parser::new();
+    parser.feed("# foo\nbar\n");
+    let blocks = parser.closed_blocks();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].to_string(), "<h1>foo</h1>\n");
+
+    // the paragraph stays open until a blank line
+    parser.feed("baz\n");
+    assert!(parser.closed_blocks().is_empty());
+    parser.feed("\n- a\n");
+    let blocks = parser.closed_blocks();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].to_string(), "<p>bar\nbaz</p>\n");
+
+    parser.feed("- b");
+    assert!(parser.closed_blocks().is_empty());
+    assert_eq!(
+        parser.finish().to_string(),
+        "<ul>\n<li>a</li>\n<li>b</li>\n</ul>\n"
+    );
+}
+
+#[test]
+fn closed_blocks_use_earlier_definitions() {
+    let mut parser = Parser::new();
+    parser.feed("[foo]: /url\n\n[foo]\n\n");
+    let blocks = parser.closed_blocks();
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].to_string(), "<p><a href=\"/url\">foo</a></p>\n");
+    parser.feed("[foo]");
+    assert_eq!(
+        parser.finish().to_string(),
+        "<p><a href=\"/url\">foo</a></p>\n"
+    );
+}
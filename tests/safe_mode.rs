@@ -0,0 +1,61 @@
+use markdown::Options;
+
+#[test]
+fn raw_html_omitted_by_default() {
+    let html = markdown::to_html("<script>alert(1)</script>\n\nfoo <b>bar</b>");
+    assert_eq!(
+        html,
+        concat!(
+            "<!-- raw HTML omitted -->\n",
+            "<p>foo <!-- raw HTML omitted -->bar<!-- raw HTML omitted --></p>\n",
+        )
+    );
+}
+
+#[test]
+fn raw_html_escaped() {
+    let options = Options::new().escape_html(true);
+    let html = markdown::to_html_with_options("<div>\n\nfoo <b>bar</b>", &options);
+    assert_eq!(html, "&lt;div&gt;\n<p>foo &lt;b&gt;bar&lt;/b&gt;</p>\n");
+}
+
+#[test]
+fn raw_html_allowed() {
+    let options = Options::new().unsafe_html(true);
+    let html = markdown::to_html_with_options("<div>\n\nfoo <b>bar</b>", &options);
+    assert_eq!(html, "<div>\n<p>foo <b>bar</b></p>\n");
+}
+
+#[test]
+fn dangerous_urls_filtered_by_default() {
+    let html = markdown::to_html(concat!(
+        "[a](javascript:alert(1)) [b](VBScript:x) [c](file:///etc/passwd) ",
+        "[d](data:text/html,x) <javascript:alert(1)> [e](https://example.com)",
+    ));
+    assert_eq!(
+        html,
+        concat!(
+            "<p><a href=\"\">a</a> <a href=\"\">b</a> <a href=\"\">c</a> ",
+            "<a href=\"\">d</a> <a href=\"\">javascript:alert(1)</a> ",
+            "<a href=\"https://example.com\">e</a></p>\n",
+        )
+    );
+}
+
+#[test]
+fn data_images_allowed() {
+    let html = markdown::to_html("![a](data:image/png;base64,AA) ![b](data:text/html,x)");
+    assert_eq!(
+        html,
+        "<p><img src=\"data:image/png;base64,AA\" alt=\"a\" /> <img src=\"\" alt=\"b\" /></p>\n"
+    );
+    let html = markdown::to_html("[a](data:image/png;base64,AA)");
+    assert_eq!(html, "<p><a href=\"\">a</a></p>\n");
+}
+
+#[test]
+fn dangerous_urls_allowed() {
+    let options = Options::new().unsafe_html(true);
+    let html = markdown::to_html_with_options("[a](javascript:alert(1))", &options);
+    assert_eq!(html, "<p><a href=\"javascript:alert(1)\">a</a></p>\n");
+}
@@ -1,9 +1,8 @@
 mod macros;
 
 // 80
-mdtest_ignore!(
+mdtest!(
     simple,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     Foo *bar*
@@ -19,15 +18,13 @@ mdtest_ignore!(
 );
 
 // 81
-mdtest_ignore!(
+mdtest!(
     multiline,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     Foo *bar
     baz*
     ====
-    ---------
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <h1>Foo <em>bar
     baz</em></h1>
@@ -36,13 +33,12 @@ mdtest_ignore!(
 );
 
 // 82
-mdtest_ignore!(
+mdtest!(
     content,
-    "emphasis not supported",
     "
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     Foo *bar
-    baz*→
+    baz*\t
     ====
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     <h1>Foo <em>bar
@@ -183,9 +179,8 @@ mdtest!(
 );
 
 // 91
-mdtest_ignore!(
+mdtest!(
     block_precedes_inline,
-    "inline not supported",
     r#"
     ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
     `Foo
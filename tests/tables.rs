@@ -0,0 +1,314 @@
+mod macros;
+
+// gfm 198
+mdtest!(
+    table,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | foo | bar |
+    | --- | --- |
+    | baz | bim |
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <table>
+    <thead>
+    <tr>
+    <th>foo</th>
+    <th>bar</th>
+    </tr>
+    </thead>
+    <tbody>
+    <tr>
+    <td>baz</td>
+    <td>bim</td>
+    </tr>
+    </tbody>
+    </table>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+// gfm 199
+mdtest!(
+    table_alignment,
+    markdown::Options::new().tables(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | abc | defghi |
+    :-: | -----------:
+    bar | baz
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <table>
+    <thead>
+    <tr>
+    <th align="center">abc</th>
+    <th align="right">defghi</th>
+    </tr>
+    </thead>
+    <tbody>
+    <tr>
+    <td align="center">bar</td>
+    <td align="right">baz</td>
+    </tr>
+    </tbody>
+    </table>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+// gfm 200
+mdtest!(
+    table_escaped_pipes,
+    markdown::Options::new().tables(true),
+    r"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | f\|oo  |
+    | ------ |
+    | b `\|` az |
+    | b **\|** im |
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <table>
+    <thead>
+    <tr>
+    <th>f|oo</th>
+    </tr>
+    </thead>
+    <tbody>
+    <tr>
+    <td>b <code>|</code> az</td>
+    </tr>
+    <tr>
+    <td>b <strong>|</strong> im</td>
+    </tr>
+    </tbody>
+    </table>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+// gfm 201
+mdtest!(
+    table_interrupted_by_block_quote,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | abc | def |
+    | --- | --- |
+    | bar | baz |
+    > bar
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <table>
+    <thead>
+    <tr>
+    <th>abc</th>
+    <th>def</th>
+    </tr>
+    </thead>
+    <tbody>
+    <tr>
+    <td>bar</td>
+    <td>baz</td>
+    </tr>
+    </tbody>
+    </table>
+    <blockquote>
+    <p>bar</p>
+    </blockquote>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+// gfm 202
+mdtest!(
+    table_ends_at_blank_line,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | abc | def |
+    | --- | --- |
+    | bar | baz |
+    bar
+
+    bar
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <table>
+    <thead>
+    <tr>
+    <th>abc</th>
+    <th>def</th>
+    </tr>
+    </thead>
+    <tbody>
+    <tr>
+    <td>bar</td>
+    <td>baz</td>
+    </tr>
+    <tr>
+    <td>bar</td>
+    <td></td>
+    </tr>
+    </tbody>
+    </table>
+    <p>bar</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+// gfm 203
+mdtest!(
+    table_header_must_match_delimiter_row,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | abc | def |
+    | --- |
+    | bar |
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>| abc | def |
+    | --- |
+    | bar |</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+// gfm 204
+mdtest!(
+    table_row_cell_count,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | abc | def |
+    | --- | --- |
+    | bar |
+    | bar | baz | boo |
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <table>
+    <thead>
+    <tr>
+    <th>abc</th>
+    <th>def</th>
+    </tr>
+    </thead>
+    <tbody>
+    <tr>
+    <td>bar</td>
+    <td></td>
+    </tr>
+    <tr>
+    <td>bar</td>
+    <td>baz</td>
+    </tr>
+    </tbody>
+    </table>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+// gfm 205
+mdtest!(
+    table_without_body,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | abc | def |
+    | --- | --- |
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <table>
+    <thead>
+    <tr>
+    <th>abc</th>
+    <th>def</th>
+    </tr>
+    </thead>
+    </table>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    table_interrupts_paragraph,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    foo
+    a | b
+    --- | ---
+    c | d
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>foo</p>
+    <table>
+    <thead>
+    <tr>
+    <th>a</th>
+    <th>b</th>
+    </tr>
+    </thead>
+    <tbody>
+    <tr>
+    <td>c</td>
+    <td>d</td>
+    </tr>
+    </tbody>
+    </table>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    table_in_block_quote,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    > a | b
+    > --- | ---
+    > c | *d*
+    >
+    > e
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <blockquote>
+    <table>
+    <thead>
+    <tr>
+    <th>a</th>
+    <th>b</th>
+    </tr>
+    </thead>
+    <tbody>
+    <tr>
+    <td>c</td>
+    <td><em>d</em></td>
+    </tr>
+    </tbody>
+    </table>
+    <p>e</p>
+    </blockquote>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    table_without_columns,
+    markdown::Options::new().tables(true),
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    |
+    |
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>|
+    |</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
+
+mdtest!(
+    tables_disabled,
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    | a | b |
+    | - | - |
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <p>| a | b |
+    | - | - |</p>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
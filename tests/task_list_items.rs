@@ -0,0 +1,120 @@
+mod macros;
+
+// gfm 279
+mdtest!(
+    task_list,
+    markdown::Options::new().tasklist(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    - [ ] foo
+    - [x] bar
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <ul>
+    <li><input disabled="" type="checkbox"> foo</li>
+    <li><input checked="" disabled="" type="checkbox"> bar</li>
+    </ul>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+// gfm 280
+mdtest!(
+    nested_task_list,
+    markdown::Options::new().tasklist(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    - [x] foo
+      - [ ] bar
+      - [x] baz
+    - [ ] bim
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <ul>
+    <li><input checked="" disabled="" type="checkbox"> foo
+    <ul>
+    <li><input disabled="" type="checkbox"> bar</li>
+    <li><input checked="" disabled="" type="checkbox"> baz</li>
+    </ul>
+    </li>
+    <li><input disabled="" type="checkbox"> bim</li>
+    </ul>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    loose_task_list,
+    markdown::Options::new().tasklist(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    1. [x] foo
+
+    2. [ ] *bar*
+       baz
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <ol>
+    <li>
+    <p><input checked="" disabled="" type="checkbox"> foo</p>
+    </li>
+    <li>
+    <p><input disabled="" type="checkbox"> <em>bar</em>
+    baz</p>
+    </li>
+    </ol>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    not_task_list,
+    markdown::Options::new().tasklist(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    - [ ]
+    - [x]foo
+    - > [ ] bar
+    - foo [ ] bar
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <ul>
+    <li>[ ]</li>
+    <li>[x]foo</li>
+    <li>
+    <blockquote>
+    <p>[ ] bar</p>
+    </blockquote>
+    </li>
+    <li>foo [ ] bar</li>
+    </ul>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    interactive_task_list,
+    markdown::Options::new()
+        .tasklist(true)
+        .interactive_tasklist(true),
+    r#"
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    - [ ] foo
+    - [x] bar
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <ul>
+    <li><input type="checkbox" data-task-index="0"> foo</li>
+    <li><input checked="" type="checkbox" data-task-index="1"> bar</li>
+    </ul>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "#
+);
+
+mdtest!(
+    task_list_disabled,
+    "
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    - [ ] foo
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    <ul>
+    <li>[ ] foo</li>
+    </ul>
+    ~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~~
+    "
+);
@@ -0,0 +1,167 @@
+use markdown::{
+    ast::{Node, Point, Position},
+    toc::{Toc, TocEntry},
+    Options, Slugger,
+};
+
+/// Parse text with heading IDs.
+fn parse(text: &str) -> Node {
+    markdown::parse_with_options(text, &Options::new().heading_ids(true))
+}
+
+/// Returns the levels and IDs of entries and their children, in order.
+fn outline(entries: &[TocEntry]) -> Vec<(u8, &str, usize)> {
+    let mut output = vec![];
+    for entry in entries {
+        let id = entry.id.as_deref().unwrap_or_default();
+        output.push((entry.level, id, entry.children.len()));
+        output.extend(outline(&entry.children));
+    }
+    output
+}
+
+#[test]
+fn nested_entries() {
+    let root = parse("# foo\n## bar\n### baz\n## qux\n# end");
+    let toc = Toc::new(&root);
+    assert_eq!(toc.entries.len(), 2);
+    assert_eq!(
+        outline(&toc.entries),
+        vec![
+            (1, "foo", 2),
+            (2, "bar", 1),
+            (3, "baz", 0),
+            (2, "qux", 0),
+            (1, "end", 0)
+        ]
+    );
+}
+
+#[test]
+fn skipped_levels() {
+    let root = parse("## intro\n# foo\n### bar\n## baz");
+    let toc = Toc::new(&root);
+    assert_eq!(
+        outline(&toc.entries),
+        vec![(2, "intro", 0), (1, "foo", 2), (3, "bar", 0), (2, "baz", 0)]
+    );
+}
+
+#[test]
+fn depth() {
+    let root = parse("# foo\n## bar\n### baz\n#### qux\n## foo");
+    let toc = Toc::with_depth(&root, 2, 3);
+    assert_eq!(
+        outline(&toc.entries),
+        vec![(2, "bar", 1), (3, "baz", 0), (2, "foo-1", 0)]
+    );
+}
+
+#[test]
+fn text_and_position() {
+    let root = parse("> Foo *bar*\n> `baz`\n> ---");
+    let toc = Toc::new(&root);
+    let entry = &toc.entries[0];
+    assert_eq!(entry.text, "Foo bar baz");
+    assert_eq!(entry.id.as_deref(), Some("foo-barbaz"));
+    assert_eq!(
+        entry.position,
+        Position::new(Point::new(1, 3, 2), Point::new(3, 6, 25))
+    );
+}
+
+#[test]
+fn heading_ids() {
+    let root = parse("# foo\n# foo\n# foo-1");
+    let toc = Toc::new(&root);
+    assert_eq!(
+        outline(&toc.entries),
+        vec![(1, "foo", 0), (1, "foo-1", 0), (1, "foo-1-1", 0)]
+    );
+}
+
+#[test]
+fn custom_slugger() {
+    let slugger = Slugger::new(|text| text.to_uppercase());
+    let options = Options::new().heading_ids(true).slugger(slugger);
+    let root = markdown::parse_with_options("# foo\n## bar", &options);
+    let html = Toc::new(&root).to_html();
+    assert!(html.contains("<a href=\"#FOO\">foo</a>"));
+    assert!(html.contains("<a href=\"#BAR\">bar</a>"));
+}
+
+#[test]
+fn explicit_ids() {
+    let options = Options::new().heading_ids(true).attributes(true);
+    let root = markdown::parse_with_options("# foo\n# bar {#foo-1}\n# foo", &options);
+    let toc = Toc::new(&root);
+    assert_eq!(
+        outline(&toc.entries),
+        vec![(1, "foo", 0), (1, "foo-1", 0), (1, "foo-2", 0)]
+    );
+    let html = root.to_string();
+    for entry in &toc.entries {
+        let id = entry.id.as_deref().unwrap_or_default();
+        assert!(html.contains(&format!("<h1 id=\"{id}\">")));
+    }
+}
+
+#[test]
+fn without_ids() {
+    let root = markdown::parse("# foo\n## bar");
+    let toc = Toc::new(&root);
+    assert_eq!(toc.entries[0].id, None);
+    assert_eq!(
+        toc.to_html(),
+        "<ul>\n\
+         <li>foo\n\
+         <ul>\n\
+         <li>bar</li>\n\
+         </ul>\n\
+         </li>\n\
+         </ul>\n"
+    );
+}
+
+#[test]
+fn to_html() {
+    let root = parse("# foo\n## bar & baz\n# qux");
+    let html = Toc::new(&root).to_html();
+    assert_eq!(
+        html,
+        "<ul>\n\
+         <li><a href=\"#foo\">foo</a>\n\
+         <ul>\n\
+         <li><a href=\"#bar--baz\">bar &amp; baz</a></li>\n\
+         </ul>\n\
+         </li>\n\
+         <li><a href=\"#qux\">qux</a></li>\n\
+         </ul>\n"
+    );
+    assert_eq!(Toc::new(&parse("foo")).to_html(), "");
+}
+
+#[test]
+fn replace_marker() {
+    let mut root = parse("[[toc]]\n\n# foo\n\n> [[toc]]");
+    Toc::new(&root).replace_marker(&mut root);
+    assert_eq!(
+        root.to_string(),
+        "<ul>\n\
+         <li><a href=\"#foo\">foo</a></li>\n\
+         </ul>\n\
+         <h1 id=\"foo\">foo</h1>\n\
+         <blockquote>\n\
+         <ul>\n\
+         <li><a href=\"#foo\">foo</a></li>\n\
+         </ul>\n\
+         </blockquote>\n"
+    );
+}
+
+#[test]
+fn replace_marker_without_headings() {
+    let mut root = parse("foo\n\n[[toc]]\n\n[[toc]] bar");
+    Toc::new(&root).replace_marker(&mut root);
+    assert_eq!(root.to_string(), "<p>foo</p>\n<p>[[toc]] bar</p>\n");
+}
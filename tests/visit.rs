@@ -0,0 +1,62 @@
+use markdown::{
+    ast::{Emphasis, Image, Link, Node, Text},
+    visit::{fold_children, walk_children, Fold, Visitor, VisitorMut},
+};
+
+#[test]
+fn visitor() {
+    struct Collect(Vec<String>);
+
+    impl Visitor for Collect {
+        fn visit_link(&mut self, link: &Link) {
+            self.0.push(link.destination.clone());
+            walk_children(self, &link.children);
+        }
+
+        fn visit_text(&mut self, text: &Text) {
+            self.0.push(text.text.clone());
+        }
+    }
+
+    let root = markdown::parse("# foo\n\n> [bar](/url) *baz*");
+    let mut collect = Collect(vec![]);
+    collect.visit_node(&root);
+    assert_eq!(collect.0, vec!["foo", "/url", "bar", " ", "baz"]);
+}
+
+#[test]
+fn visitor_mut() {
+    struct Rewrite;
+
+    impl VisitorMut for Rewrite {
+        fn visit_link_mut(&mut self, link: &mut Link) {
+            link.destination = format!("https://example.com{}", link.destination);
+        }
+    }
+
+    let mut root = markdown::parse("- [foo](/bar)");
+    Rewrite.visit_node_mut(&mut root);
+    assert_eq!(
+        root.to_string(),
+        "<ul>\n<li><a href=\"https://example.com/bar\">foo</a></li>\n</ul>\n"
+    );
+}
+
+#[test]
+fn fold() {
+    struct Strip;
+
+    impl Fold for Strip {
+        fn fold_image(&mut self, _image: Image) -> Vec<Node> {
+            vec![]
+        }
+
+        fn fold_emphasis(&mut self, emphasis: Emphasis) -> Vec<Node> {
+            fold_children(self, emphasis.children)
+        }
+    }
+
+    let root = markdown::parse("foo ![bar](/url) *baz **qux***");
+    let root = Strip.fold_node(root).pop().expect("root should be kept");
+    assert_eq!(root.to_string(), "<p>foo  baz <strong>qux</strong></p>\n");
+}